@@ -0,0 +1,27 @@
+//! Compares [`HTree::collect_all`] to collecting [`HTree::into_iter`].
+//!
+//! Collecting all 131071 lines of an order 16 HTree of `f64` measured with `cargo bench`, the
+//! iterator being noisy between runs:
+//!
+//! | method                  | time          |
+//! |-------------------------|---------------|
+//! | `into_iter().collect()` | 2.4 - 3.9 ms  |
+//! | `collect_all()`         | 0.74 ms       |
+//!
+//! so `collect_all` is about 3 to 5 times faster.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use htree::HTree;
+
+fn collect(c: &mut Criterion) {
+    let htree: HTree<f64> = HTree::new(16);
+    let mut group = c.benchmark_group("collect order 16");
+    group.bench_function("into_iter().collect()", |b| {
+        b.iter(|| black_box(&htree).into_iter().collect::<Vec<_>>())
+    });
+    group.bench_function("collect_all()", |b| b.iter(|| black_box(&htree).collect_all()));
+    group.finish();
+}
+
+criterion_group!(benches, collect);
+criterion_main!(benches);
@@ -0,0 +1,36 @@
+//! Generation of random HTrees for fuzzing with [arbitrary](https://docs.rs/arbitrary).
+
+use crate::{HTree, MAX_SUPPORTED_ORDER};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use num::Float;
+
+/// Largest number of branches of a generated HTree.
+const MAX_BRANCHES: u32 = 4;
+
+impl<'a, T> Arbitrary<'a> for HTree<T>
+where
+    T: Float,
+{
+    /// Returns an HTree which can be iterated, with 2 to 4 branches and an order small enough that
+    /// `branches^(order+1)` fits into `u32`, at most [`MAX_SUPPORTED_ORDER`].
+    ///
+    /// The bounding box is either given by an arbitrary [`Shape`](crate::Shape) or an explicit size
+    /// of 1 to 65535 in both directions.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let branches = u.int_in_range(2..=MAX_BRANCHES)?;
+        let max_order = (0..=MAX_SUPPORTED_ORDER)
+            .take_while(|&order| branches.checked_pow(order as u32 + 1).is_some())
+            .last()
+            .unwrap_or(0);
+        let order = u.int_in_range(0..=max_order)?;
+        let mut h_tree = HTree::with_branching(order, branches)
+            .with_shape(u.arbitrary()?)
+            .with_emission_order(u.arbitrary()?);
+        if u.arbitrary()? {
+            let width = T::from(u.int_in_range(1..=u16::MAX)?).unwrap();
+            let height = T::from(u.int_in_range(1..=u16::MAX)?).unwrap();
+            h_tree.size = Some((width, height));
+        }
+        Ok(h_tree)
+    }
+}
@@ -0,0 +1,59 @@
+//! Export of HTrees as cubic Bezier curves.
+
+use crate::HTree;
+use num::Float;
+
+/// Cubic Bezier curve from `start` to `end` pulled towards `control1` and `control2`, see [`to_bezier_path`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BezierSeg {
+    /// Start of the curve, reached at `t = 0`.
+    pub start: (f64, f64),
+    /// Control point next to the start.
+    pub control1: (f64, f64),
+    /// Control point next to the end.
+    pub control2: (f64, f64),
+    /// End of the curve, reached at `t = 1`.
+    pub end: (f64, f64),
+}
+
+impl BezierSeg {
+    /// Returns the point of the curve at parameter `t` in `[0, 1]`.
+    pub fn eval(&self, t: f64) -> (f64, f64) {
+        let s = 1.0 - t;
+        let weights = [s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t];
+        let points = [self.start, self.control1, self.control2, self.end];
+        weights
+            .iter()
+            .zip(points)
+            .fold((0.0, 0.0), |sum, (weight, point)| (sum.0 + weight * point.0, sum.1 + weight * point.1))
+    }
+}
+
+/// Returns the lines of the HTree as straight cubic Bezier curves forming a single path.
+///
+/// The control points of every line lie at `1/3` and `2/3` along it, so the curve is traversed at
+/// constant speed. Curves are returned in the order yielded by [`HTree::into_iter`].
+///
+/// # Examples
+///
+/// ```
+/// use htree::{to_bezier_path, HTree};
+/// let htree: HTree<f32> = HTree::with_size(0, 12.0, 4.0);
+/// let curve = to_bezier_path(&htree)[0];
+/// assert_eq!((curve.control1, curve.control2), ((5.0, 2.0), (7.0, 2.0)));
+/// assert_eq!(curve.eval(0.5), (6.0, 2.0));
+/// ```
+pub fn to_bezier_path<T: Float + Into<f64>>(tree: &HTree<T>) -> Vec<BezierSeg> {
+    tree.into_iter()
+        .map(|(start, stop)| {
+            let (start, end) = ((start.0.into(), start.1.into()), (stop.0.into(), stop.1.into()));
+            let along = |t: f64| (start.0 + (end.0 - start.0) * t, start.1 + (end.1 - start.1) * t);
+            BezierSeg {
+                start,
+                control1: along(1.0 / 3.0),
+                control2: along(2.0 / 3.0),
+                end,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,28 @@
+//! Export of HTrees as comma separated values.
+
+use crate::HTree;
+use num::Float;
+use std::fmt::Display;
+use std::io::{self, Write};
+
+/// Writes the lines of the HTree as CSV with the header `x0,y0,x1,y1` and one row per line.
+///
+/// Rows are written one by one in the order yielded by [`HTree::into_iter`], wrap slow writers in
+/// a [`std::io::BufWriter`].
+///
+/// # Examples
+///
+/// ```
+/// use htree::{write_csv, HTree};
+/// let htree: HTree<f32> = HTree::new(0);
+/// let mut csv = Vec::new();
+/// write_csv(&htree, &mut csv).unwrap();
+/// assert_eq!(String::from_utf8(csv).unwrap(), "x0,y0,x1,y1\n0.25,0.35355338,0.75,0.35355338\n");
+/// ```
+pub fn write_csv<W: Write, T: Float + Display>(tree: &HTree<T>, w: &mut W) -> io::Result<()> {
+    writeln!(w, "x0,y0,x1,y1")?;
+    for (start, stop) in tree {
+        writeln!(w, "{},{},{},{}", start.0, start.1, stop.0, stop.1)?;
+    }
+    Ok(())
+}
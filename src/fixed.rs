@@ -0,0 +1,130 @@
+//! H Tree computed in fixed point arithmetic for bit identical results on every platform.
+//!
+//! Coordinates are `i64` with [`FRACTION_BITS`] fractional bits. Only integer operations are used,
+//! so the lines are the same everywhere and can be converted to floating point at the end.
+
+/// Number of fractional bits of the fixed point coordinates.
+pub const FRACTION_BITS: u32 = 32;
+
+/// Height of the bounding box `1/sqrt(2)` in fixed point, rounded to nearest.
+const SCALE_HEIGHT: i64 = 3_037_000_500;
+
+/// Line given by its start and stop point in fixed point coordinates.
+pub type FixedLine = ((i64, i64), (i64, i64));
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HTreeFixed {
+    order: usize,
+}
+
+pub struct HTreeFixedIterator {
+    h_tree: HTreeFixed,
+    index: usize,
+}
+
+impl HTreeFixed {
+    /// Returns an instance of a fixed point HTree up to specified order.
+    ///
+    /// The lines are those of [`crate::HTree::new`] with coordinates rounded to [`FRACTION_BITS`]
+    /// fractional bits, x coordinates are exact.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the order exceeds [`crate::MAX_SUPPORTED_ORDER`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::fixed::{to_f64, HTreeFixed};
+    /// let htree=HTreeFixed::new(3);
+    /// let ((x,_),_)=htree.into_iter().next().unwrap();
+    /// assert_eq!(to_f64(x),0.25);
+    /// ```
+    pub fn new(order: usize) -> HTreeFixed {
+        assert!(crate::is_order_supported(order), "order {order} is not supported");
+        HTreeFixed { order }
+    }
+
+    /// Returns the order of the HTree.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Returns an iterator over the lines of the HTree converted to `f64`.
+    pub fn lines_f64(&self) -> impl Iterator<Item = crate::Line<f64>> {
+        self.into_iter()
+            .map(|((a, b), (c, d))| ((to_f64(a), to_f64(b)), (to_f64(c), to_f64(d))))
+    }
+
+    /// Returns an iterator over the lines of the HTree converted to `f32`.
+    pub fn lines_f32(&self) -> impl Iterator<Item = crate::Line<f32>> {
+        self.into_iter()
+            .map(|((a, b), (c, d))| ((to_f32(a), to_f32(b)), (to_f32(c), to_f32(d))))
+    }
+
+    /// Returns the line with index `iteration_index` within level `order_index`.
+    fn segment(&self, order_index: u32, iteration_index: u32) -> FixedLine {
+        // same grid as `HTree::segment` with two rectangles per line
+        let num_horizontal_rectangles = 1u32 << (order_index / 2 + 1);
+        let num_vertical_rectangles = 1u32 << order_index.div_ceil(2);
+        let rectangle_index = 2 * iteration_index;
+        let cell = |rectangle: u32| {
+            if order_index % 2 == 1 {
+                (rectangle / num_vertical_rectangles, rectangle % num_vertical_rectangles)
+            } else {
+                (rectangle % num_horizontal_rectangles, rectangle / num_horizontal_rectangles)
+            }
+        };
+        let point = |(x, y): (u32, u32)| {
+            (
+                center(x, num_horizontal_rectangles),
+                ((center(y, num_vertical_rectangles) as i128 * SCALE_HEIGHT as i128 + (1 << (FRACTION_BITS - 1)))
+                    >> FRACTION_BITS) as i64,
+            )
+        };
+        (point(cell(rectangle_index)), point(cell(rectangle_index + 1)))
+    }
+}
+
+/// Returns the center `(cell + 0.5) / count` of a cell in fixed point, exact as `count` is a power of two.
+fn center(cell: u32, count: u32) -> i64 {
+    ((2 * cell as i64 + 1) << (FRACTION_BITS - 1)) / count as i64
+}
+
+/// Converts a fixed point coordinate to `f64`, exact up to 53 significant bits.
+pub fn to_f64(value: i64) -> f64 {
+    value as f64 / (1u64 << FRACTION_BITS) as f64
+}
+
+/// Converts a fixed point coordinate to `f32`, rounded to nearest.
+pub fn to_f32(value: i64) -> f32 {
+    to_f64(value) as f32
+}
+
+impl Iterator for HTreeFixedIterator {
+    type Item = FixedLine;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.index += 1;
+        let order_index = self.index.ilog2();
+        if order_index > self.h_tree.order as u32 {
+            return None;
+        }
+        let iteration_index = (self.index - (1usize << order_index)) as u32;
+        Some(self.h_tree.segment(order_index, iteration_index))
+    }
+}
+
+impl IntoIterator for HTreeFixed {
+    type Item = FixedLine;
+    type IntoIter = HTreeFixedIterator;
+
+    /// Returns an HTreeFixedIterator which iterates over lines of the fixed point HTree.
+    ///
+    /// Lines are yielded in the same order as by [`crate::HTree::into_iter`].
+    fn into_iter(self) -> Self::IntoIter {
+        HTreeFixedIterator {
+            h_tree: self,
+            index: 0,
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Vertex graph over the lines of an HTree.
+
+use crate::{interior_crossing, HTree};
+use num::Float;
+use std::collections::HashMap;
+
+/// Planar graph whose vertices are the distinct endpoints and junctions of the lines of an HTree.
+///
+/// Lines of an HTree never share endpoints, instead the endpoints of one level touch the middle of
+/// the lines of the next level. Lines are therefore split wherever another endpoint touches their
+/// interior or, with more than 2 branches, where another line crosses them, so its edges only meet
+/// at vertices.
+pub(crate) struct Graph<T> {
+    pub(crate) vertices: Vec<(T, T)>,
+    pub(crate) edges: Vec<(usize, usize)>,
+    /// Index of the line every edge is a piece of.
+    pub(crate) lines: Vec<usize>,
+}
+
+impl<T> Graph<T>
+where
+    T: Float,
+{
+    pub(crate) fn new(h_tree: &HTree<T>) -> Graph<T> {
+        let epsilon = epsilon(h_tree);
+        let mut vertices = Vertices::new(epsilon);
+        let mut edges = Vec::new();
+        let mut lines = Vec::new();
+        for (line, (start, stop)) in h_tree.into_iter().enumerate() {
+            let min = (start.0.min(stop.0) - epsilon, start.1.min(stop.1) - epsilon);
+            let max = (start.0.max(stop.0) + epsilon, start.1.max(stop.1) + epsilon);
+            // lines end on other lines, and with more than 2 branches the middle children cross them
+            let mut splits: Vec<T> = h_tree
+                .segments_in_rect(min, max)
+                .filter(|&other| other != (start, stop))
+                .flat_map(|other| {
+                    let crossing = interior_crossing((start, stop), other, epsilon);
+                    [Some(other.0), Some(other.1), crossing].into_iter().flatten()
+                })
+                .filter_map(|point| interior_parameter((start, stop), point, epsilon))
+                .collect();
+            splits.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            splits.dedup_by(|a, b| (*a - *b).abs() <= epsilon);
+
+            let mut previous = vertices.insert(start);
+            for t in splits {
+                let point = (start.0 + (stop.0 - start.0) * t, start.1 + (stop.1 - start.1) * t);
+                let current = vertices.insert(point);
+                edges.push((previous, current));
+                lines.push(line);
+                previous = current;
+            }
+            edges.push((previous, vertices.insert(stop)));
+            lines.push(line);
+        }
+        Graph {
+            vertices: vertices.points,
+            edges,
+            lines,
+        }
+    }
+
+    /// Returns for every vertex a representative vertex shared by all vertices of its connected component.
+    pub(crate) fn components(&self) -> Vec<usize> {
+        fn find(parents: &mut [usize], vertex: usize) -> usize {
+            let mut root = vertex;
+            while parents[root] != root {
+                root = parents[root];
+            }
+            let mut current = vertex;
+            while parents[current] != root {
+                current = std::mem::replace(&mut parents[current], root);
+            }
+            root
+        }
+
+        let mut parents: Vec<usize> = (0..self.vertices.len()).collect();
+        for &(a, b) in &self.edges {
+            let (a, b) = (find(&mut parents, a), find(&mut parents, b));
+            parents[a] = b;
+        }
+        (0..self.vertices.len()).map(|vertex| find(&mut parents, vertex)).collect()
+    }
+
+    /// Returns for every vertex the indices of the edges ending in it.
+    pub(crate) fn adjacency(&self) -> Vec<Vec<usize>> {
+        let mut adjacency = vec![Vec::new(); self.vertices.len()];
+        for (edge, &(a, b)) in self.edges.iter().enumerate() {
+            adjacency[a].push(edge);
+            adjacency[b].push(edge);
+        }
+        adjacency
+    }
+
+    /// Covers all edges with chains of vertices, walking on through junctions as straight as possible.
+    ///
+    /// Walks start at tips and then at other vertices of odd degree since every such vertex has to end a chain.
+    pub(crate) fn polylines(&self) -> Vec<Vec<usize>> {
+        let adjacency = self.adjacency();
+        let mut used = vec![false; self.edges.len()];
+        let mut starts: Vec<usize> = (0..self.vertices.len()).collect();
+        starts.sort_by_key(|&vertex| (adjacency[vertex].len().is_multiple_of(2), adjacency[vertex].len()));
+
+        let mut polylines = Vec::new();
+        for start in starts {
+            while let Some(&first_edge) = adjacency[start].iter().find(|&&edge| !used[edge]) {
+                let mut polyline = vec![start];
+                let mut current = start;
+                let mut edge = first_edge;
+                loop {
+                    used[edge] = true;
+                    let next = self.other(edge, current);
+                    polyline.push(next);
+                    let direction = self.direction(current, next);
+                    current = next;
+                    let straightest = adjacency[current]
+                        .iter()
+                        .filter(|&&candidate| !used[candidate])
+                        .map(|&candidate| {
+                            let turn = self.direction(current, self.other(candidate, current));
+                            (candidate, direction.0 * turn.0 + direction.1 * turn.1)
+                        })
+                        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+                    match straightest {
+                        Some((candidate, _)) => edge = candidate,
+                        None => break,
+                    }
+                }
+                polylines.push(polyline);
+            }
+        }
+        polylines
+    }
+
+    /// Covers all edges with chains of vertices found by a depth first walk from the first vertex.
+    ///
+    /// A chain ends whenever the walk gets stuck and has to back up to an earlier vertex.
+    pub(crate) fn depth_first(&self) -> Vec<Vec<usize>> {
+        let adjacency = self.adjacency();
+        let mut used = vec![false; self.edges.len()];
+        let mut chains: Vec<Vec<usize>> = Vec::new();
+        for start in 0..self.vertices.len() {
+            let mut stack = vec![start];
+            while let Some(&current) = stack.last() {
+                match adjacency[current].iter().find(|&&edge| !used[edge]) {
+                    Some(&edge) => {
+                        used[edge] = true;
+                        let next = self.other(edge, current);
+                        match chains.last_mut() {
+                            Some(chain) if chain.last() == Some(&current) => chain.push(next),
+                            _ => chains.push(vec![current, next]),
+                        }
+                        stack.push(next);
+                    }
+                    None => {
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        chains
+    }
+
+    fn other(&self, edge: usize, vertex: usize) -> usize {
+        let (a, b) = self.edges[edge];
+        if a == vertex {
+            b
+        } else {
+            a
+        }
+    }
+
+    fn direction(&self, from: usize, to: usize) -> (T, T) {
+        let (from, to) = (self.vertices[from], self.vertices[to]);
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let length = dx.hypot(dy);
+        (dx / length, dy / length)
+    }
+}
+
+/// Distance below which two points of an HTree are considered equal.
+pub(crate) fn epsilon<T: Float>(h_tree: &HTree<T>) -> T {
+    h_tree.segment_length(h_tree.order as u32) / T::from(1000).unwrap()
+}
+
+/// Returns the parameter `t` in `(0, 1)` at which `point` lies on the interior of `segment`.
+fn interior_parameter<T: Float>(segment: ((T, T), (T, T)), point: (T, T), epsilon: T) -> Option<T> {
+    let (start, stop) = segment;
+    let (dx, dy) = (stop.0 - start.0, stop.1 - start.1);
+    let length = dx.hypot(dy);
+    let along = ((point.0 - start.0) * dx + (point.1 - start.1) * dy) / length;
+    let across = ((point.0 - start.0) * dy - (point.1 - start.1) * dx) / length;
+    if across.abs() > epsilon || along <= epsilon || along >= length - epsilon {
+        return None;
+    }
+    Some(along / length)
+}
+
+/// Deduplicates points closer than `epsilon` by bucketing them into cells of that size.
+struct Vertices<T> {
+    epsilon: T,
+    points: Vec<(T, T)>,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl<T> Vertices<T>
+where
+    T: Float,
+{
+    fn new(epsilon: T) -> Vertices<T> {
+        Vertices {
+            epsilon,
+            points: Vec::new(),
+            cells: HashMap::new(),
+        }
+    }
+
+    fn insert(&mut self, point: (T, T)) -> usize {
+        let cell = (
+            (point.0 / self.epsilon).floor().to_i64().unwrap(),
+            (point.1 / self.epsilon).floor().to_i64().unwrap(),
+        );
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if let Some(indices) = self.cells.get(&(cell.0 + dx, cell.1 + dy)) {
+                    for &index in indices {
+                        let other = self.points[index];
+                        if (other.0 - point.0).hypot(other.1 - point.1) <= self.epsilon {
+                            return index;
+                        }
+                    }
+                }
+            }
+        }
+        self.points.push(point);
+        self.cells.entry(cell).or_default().push(self.points.len() - 1);
+        self.points.len() - 1
+    }
+}
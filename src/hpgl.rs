@@ -0,0 +1,44 @@
+//! Export of HTrees as HPGL plotter programs.
+
+use crate::HTree;
+use num::Float;
+use std::fmt::Write;
+
+/// Returns an HPGL program plotting every level of the HTree with its own pen.
+///
+/// Level `n` is plotted with pen `n + 1`, selected by an `SP` command before its lines, and every
+/// line is drawn by lifting the pen to its start with `PU` and lowering it along to its stop with
+/// `PD`. The HTree is scaled as large as possible without distorting it and centered within
+/// `width_units x height_units` plotter units. Since the y axis of plotters points up, the HTree is
+/// flipped vertically so the plot looks like a rendered image.
+///
+/// # Examples
+///
+/// ```
+/// use htree::{to_hpgl, HTree};
+/// let htree: HTree<f32> = HTree::new(0);
+/// assert_eq!(to_hpgl(&htree, 400, 400), "IN;PA;SP1;PU100,200;PD300,200;PU;SP0;");
+/// ```
+pub fn to_hpgl<T: Float + Into<f64>>(tree: &HTree<T>, width_units: i32, height_units: i32) -> String {
+    let (extent_width, extent_height) = tree.extent();
+    let (extent_width, extent_height) = (extent_width.into(), extent_height.into());
+    let (width, height) = (f64::from(width_units), f64::from(height_units));
+    let scale = (width / extent_width).min(height / extent_height);
+    let offset = ((width - scale * extent_width) / 2.0, (height - scale * extent_height) / 2.0);
+    let unit = |point: (T, T)| {
+        let (x, y) = tree.relative(point);
+        let (x, y) = (offset.0 + x.into() * scale, offset.1 + y.into() * scale);
+        (x.round() as i64, (height - y).round() as i64)
+    };
+
+    let mut hpgl = String::from("IN;PA;");
+    for level in 0..=tree.order() {
+        let _ = write!(hpgl, "SP{};", level + 1);
+        for (start, stop) in tree.level(level) {
+            let ((x1, y1), (x2, y2)) = (unit(start), unit(stop));
+            let _ = write!(hpgl, "PU{x1},{y1};PD{x2},{y2};");
+        }
+    }
+    hpgl.push_str("PU;SP0;");
+    hpgl
+}
@@ -0,0 +1,54 @@
+//! Export of HTrees as HTML canvas snippets.
+
+use crate::HTree;
+use num::Float;
+use std::fmt::Write;
+
+/// Returns a self-contained HTML snippet of a `<canvas>` of `width` x `height` and a script drawing the HTree into it.
+///
+/// The lines are inlined as a flat JavaScript array `[x0, y0, x1, y1, ...]` of canvas pixels, one
+/// group of four numbers per line in the order yielded by [`HTree::into_iter`]. The HTree is scaled
+/// as large as possible without distorting it and centered within the canvas.
+///
+/// # Examples
+///
+/// ```
+/// use htree::{to_html_canvas, HTree};
+/// let htree: HTree<f32> = HTree::new(0);
+/// let html = to_html_canvas(&htree, 100, 100);
+/// assert!(html.starts_with("<canvas width=\"100\" height=\"100\"></canvas>"));
+/// assert!(html.contains("[25,50,75,50]"));
+/// ```
+pub fn to_html_canvas<T: Float + Into<f64>>(tree: &HTree<T>, width: u32, height: u32) -> String {
+    let (extent_width, extent_height) = tree.extent();
+    let (extent_width, extent_height) = (extent_width.into(), extent_height.into());
+    let (canvas_width, canvas_height) = (f64::from(width), f64::from(height));
+    let scale = (canvas_width / extent_width).min(canvas_height / extent_height);
+    let offset = ((canvas_width - scale * extent_width) / 2.0, (canvas_height - scale * extent_height) / 2.0);
+    let pixel = |point: (T, T)| {
+        let (x, y) = tree.relative(point);
+        (offset.0 + x.into() * scale, offset.1 + y.into() * scale)
+    };
+
+    let mut lines = String::new();
+    for (start, stop) in tree {
+        let ((x1, y1), (x2, y2)) = (pixel(start), pixel(stop));
+        let separator = if lines.is_empty() { "" } else { "," };
+        let _ = write!(lines, "{separator}{x1},{y1},{x2},{y2}");
+    }
+    format!(
+        "<canvas width=\"{width}\" height=\"{height}\"></canvas>\n\
+         <script>\n\
+         (() => {{\n\
+         const lines = [{lines}];\n\
+         const context = document.currentScript.previousElementSibling.getContext(\"2d\");\n\
+         context.beginPath();\n\
+         for (let i = 0; i < lines.length; i += 4) {{\n\
+         context.moveTo(lines[i], lines[i + 1]);\n\
+         context.lineTo(lines[i + 2], lines[i + 3]);\n\
+         }}\n\
+         context.stroke();\n\
+         }})();\n\
+         </script>\n"
+    )
+}
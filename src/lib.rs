@@ -4,66 +4,3260 @@
 //! Provides the HTree struct which can be turned into an iterator over the lines contained within the H Tree.
 //! <https://en.wikipedia.org/wiki/H_tree>
 
-#![feature(int_log)]
-
 use num::Float;
 use std::marker::PhantomData;
-const SCALE_HEIGHT: f64 = 0.7071067811865475244;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary_tree;
+mod bezier;
+mod csv;
+pub mod fixed;
+mod graph;
+mod hpgl;
+mod html;
+#[cfg(feature = "lyon")]
+mod lyon_path;
+mod outline;
+mod plot;
+#[cfg(feature = "prost")]
+mod protobuf;
+#[cfg(feature = "raqote")]
+mod raqote_draw;
+#[cfg(feature = "rayon")]
+mod rayon_iter;
+#[cfg(feature = "image")]
+mod render;
+mod spatial;
+mod stl;
+mod svg;
+pub mod three_d;
+
+pub use bezier::{to_bezier_path, BezierSeg};
+pub use csv::write_csv;
+pub use hpgl::to_hpgl;
+pub use html::to_html_canvas;
+#[cfg(feature = "lyon")]
+pub use lyon_path::path_events;
+#[cfg(feature = "prost")]
+pub use protobuf::{to_protobuf, HTreeSegments, Segment};
+#[cfg(feature = "raqote")]
+pub use raqote_draw::draw_into_raqote;
+#[cfg(feature = "image")]
+pub use render::{render_growth_frames, render_to_png, render_to_png_bytes, thumbnail};
+pub use spatial::SpatialIndex;
+pub use stl::to_extruded_stl;
+pub use svg::to_svg_layered;
+
+/// Line of an HTree given by its start and stop point.
+pub type Line<T> = ((T, T), (T, T));
+
+/// Centers of the rectangles of a level and the links between adjacent ones, see [`HTree::dual_graph`].
+pub type DualGraph<T> = (Vec<(T, T)>, Vec<(usize, usize)>);
+
+/// Largest order an HTree can be iterated up to.
+///
+/// Lines of level `l` are computed on a grid of `2^(l+1)` rectangles indexed by `u32`,
+/// which overflows beyond level 30. Trees with more branches support lower orders,
+/// see [`HTree::with_branching`].
+pub const MAX_SUPPORTED_ORDER: usize = 30;
+
+/// Returns whether an HTree of the given order can be iterated without overflowing.
+///
+/// # Examples
+///
+/// ```
+/// assert!(htree::is_order_supported(20));
+/// assert!(!htree::is_order_supported(40));
+/// ```
+pub fn is_order_supported(order: usize) -> bool {
+    order <= MAX_SUPPORTED_ORDER
+}
+
+/// Compares the lines of two HTrees, matching lines whose endpoints are within `eps` of each other.
+///
+/// Lines match regardless of their direction and every line matches at most one line of the other
+/// HTree. Lines of `b` are bucketed into cells of size `eps`, so every line of `a` is only compared
+/// with nearby lines.
+///
+/// # Examples
+///
+/// ```
+/// use htree::{geometry_diff, HTree};
+/// let diff=geometry_diff(&HTree::<f64>::new(3),&HTree::<f64>::new(4),1e-9);
+/// assert_eq!((diff.only_in_a,diff.only_in_b,diff.matching),(0,16,15));
+/// ```
+pub fn geometry_diff<T: Float>(a: &HTree<T>, b: &HTree<T>, eps: T) -> GeometryDiff {
+    let cell_size = eps.max(T::min_positive_value());
+    let cell = |(start, stop): Line<T>| {
+        let (x, y) = ((start.0 + stop.0) * half(), (start.1 + stop.1) * half());
+        (
+            (x / cell_size).floor().to_i64().unwrap_or(0),
+            (y / cell_size).floor().to_i64().unwrap_or(0),
+        )
+    };
+    let close = |p: (T, T), q: (T, T)| (p.0 - q.0).hypot(p.1 - q.1) <= eps;
+
+    let lines: Vec<Line<T>> = b.into_iter().collect();
+    let mut cells: std::collections::HashMap<(i64, i64), Vec<usize>> = std::collections::HashMap::new();
+    for (index, &line) in lines.iter().enumerate() {
+        cells.entry(cell(line)).or_default().push(index);
+    }
+    let mut matched = vec![false; lines.len()];
+    let mut matching = 0;
+    for (start, stop) in a {
+        let (x, y) = cell((start, stop));
+        let found = (-1..=1)
+            .flat_map(|dx| (-1..=1).map(move |dy| (x + dx, y + dy)))
+            .filter_map(|neighbour| cells.get(&neighbour))
+            .flatten()
+            .copied()
+            .find(|&index| {
+                let (other_start, other_stop) = lines[index];
+                !matched[index]
+                    && ((close(start, other_start) && close(stop, other_stop))
+                        || (close(start, other_stop) && close(stop, other_start)))
+            });
+        if let Some(index) = found {
+            matched[index] = true;
+            matching += 1;
+        }
+    }
+    GeometryDiff {
+        only_in_a: a.len() - matching,
+        only_in_b: lines.len() - matching,
+        matching,
+    }
+}
+
+/// Error returned when an HTree cannot be iterated as requested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HTreeError {
+    /// The order exceeds [`MAX_SUPPORTED_ORDER`], its grid indices would overflow.
+    UnsupportedOrder(usize),
+    /// Every line needs at least 2 children, fewer would not split the bounding box.
+    UnsupportedBranches(u32),
+}
+
+impl std::fmt::Display for HTreeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HTreeError::UnsupportedOrder(order) => {
+                write!(f, "order {order} exceeds the maximal supported order {MAX_SUPPORTED_ORDER}")
+            }
+            HTreeError::UnsupportedBranches(branches) => {
+                write!(f, "an HTree needs at least 2 branches, got {branches}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HTreeError {}
+
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "RawHTree<T>", bound(deserialize = "T: Float + serde::Deserialize<'de>"))
+)]
+pub struct HTree<T> {
+    order: usize,
+    branches: u32,
+    shape: Shape,
+    emission_order: EmissionOrder,
+    /// Explicit `(width, height)` of the bounding box overriding the one given by the shape.
+    size: Option<(T, T)>,
+    /// Position of the top left corner of the bounding box, see [`HTree::with_bounds`].
+    origin: (T, T),
+    /// Whether the y axis is mirrored within the bounding box, see [`HTree::with_flipped_y`].
+    flipped: bool,
+    /// Spread and seed of the random scaling of every line, see [`HTree::with_length_variation`].
+    length_variation: Option<(T, u64)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _marker: PhantomData<T>,
+}
+
+/// Fields of a deserialized HTree before they are checked like [`HTree::with_branching`] does.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawHTree<T> {
+    order: usize,
+    branches: u32,
+    shape: Shape,
+    emission_order: EmissionOrder,
+    size: Option<(T, T)>,
+    origin: (T, T),
+    flipped: bool,
+    length_variation: Option<(T, u64)>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<RawHTree<T>> for HTree<T>
+where
+    T: Float,
+{
+    type Error = HTreeError;
+
+    fn try_from(raw: RawHTree<T>) -> Result<HTree<T>, HTreeError> {
+        if raw.branches < 2 {
+            return Err(HTreeError::UnsupportedBranches(raw.branches));
+        }
+        Ok(HTree {
+            order: raw.order,
+            branches: raw.branches,
+            shape: raw.shape,
+            emission_order: raw.emission_order,
+            size: raw.size,
+            origin: raw.origin,
+            flipped: raw.flipped,
+            length_variation: raw.length_variation,
+            _marker: PhantomData {},
+        })
+    }
+}
+
+/// Shape of the bounding box an HTree is drawn into.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Shape {
+    /// Box of size `1 x 1/sqrt(2)`, every line is `1/sqrt(2)` times as long as the line of the previous level.
+    #[default]
+    Classic,
+    /// Box of size `1 x 1`, lines of odd levels are as long as the lines of the previous level
+    /// while lines of even levels are half as long.
+    /// The central crossbar and the first verticals make up a square H of side `1/2`.
+    Square,
+}
+
+/// Order in which the lines within a level of an HTree are yielded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum EmissionOrder {
+    /// Row by row on levels of horizontal lines and column by column on levels of vertical lines.
+    #[default]
+    AsBuilt,
+    /// Row by row from top to bottom, every row from left to right.
+    RowMajor,
+    /// Column by column from left to right, every column from top to bottom.
+    ColMajor,
+    /// Every line followed by the lines branching off it, children in the order they lie along the line.
+    ///
+    /// Levels are no longer yielded one after another, so this order keeps every subtree together.
+    DepthFirst,
+    /// Lines are yielded as by [`EmissionOrder::DepthFirst`].
+    ///
+    /// When plotting, see [`HTree::plot_report`], the lines are additionally split at their
+    /// junctions and joined into polylines which are drawn one after another choosing the nearest
+    /// next start.
+    Optimized,
+}
+
+/// Direction of a line of an HTree, see [`HTree::iter_with_orientation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    /// Line of constant y, on even levels.
+    Horizontal,
+    /// Line of constant x, on odd levels.
+    Vertical,
+}
+
+/// Closed form growth statistics of an HTree, see [`HTree::growth_report`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GrowthReport<T> {
+    /// Number of lines added at each level.
+    pub segments_per_level: Vec<usize>,
+    /// Length of a single line at each level.
+    pub segment_lengths: Vec<T>,
+    /// Total length of all lines up to and including each level.
+    pub cumulative_lengths: Vec<T>,
+    /// Factor by which the line length shrinks from one level to the next.
+    pub scaling_ratio: T,
+    /// Similarity dimension implied by the line counts and the scaling ratio.
+    pub fractal_dimension: T,
+}
+
+/// Differences between the lines of two HTrees, see [`geometry_diff`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GeometryDiff {
+    /// Number of lines of the first HTree without a match in the second.
+    pub only_in_a: usize,
+    /// Number of lines of the second HTree without a match in the first.
+    pub only_in_b: usize,
+    /// Number of lines matched between both HTrees.
+    pub matching: usize,
+}
+
+/// Symmetries of the bounding box which map the lines of an HTree onto themselves, see [`HTree::symmetries`].
+///
+/// Quarter turns and reflections about the diagonals only map square bounding boxes onto themselves.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Symmetries {
+    /// Reflection about the vertical center line, swapping left and right.
+    pub vertical_mirror: bool,
+    /// Reflection about the horizontal center line, swapping top and bottom.
+    pub horizontal_mirror: bool,
+    /// Rotation by 180 degrees about the center.
+    pub half_turn: bool,
+    /// Rotation by 90 degrees about the center, in either direction.
+    pub quarter_turn: bool,
+    /// Reflection about the diagonal from the top left to the bottom right corner.
+    pub diagonal_mirror: bool,
+    /// Reflection about the diagonal from the bottom left to the top right corner.
+    pub antidiagonal_mirror: bool,
+}
+
+/// Statistics on plotting an HTree with a pen, see [`HTree::plot_report`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotReport<T> {
+    /// Total length drawn with the pen down.
+    pub draw_length: T,
+    /// Total distance travelled with the pen up between strokes.
+    pub travel_length: T,
+    /// Number of times the pen is lifted between strokes.
+    pub pen_lifts: usize,
+}
+
+/// Line of an HTree with the lines branching off it, see [`HTree::to_nested`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HNode<T> {
+    /// The line itself.
+    pub segment: Line<T>,
+    /// Nodes of the lines centered on this line, ordered from its start to its stop.
+    pub children: Vec<HNode<T>>,
+}
+
+/// Lines of an HTree stored level by level, see [`HTree::level_cache`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelCache<T> {
+    levels: Vec<Vec<Line<T>>>,
+}
+
+/// Position of an [`HTreeIterator`] which can be stored to resume it later, see [`HTreeIterator::checkpoint`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IteratorCheckpoint {
+    /// Index of the next line to yield.
+    pub index: usize,
+    /// Order of the iterated HTree.
+    pub order: usize,
+}
+
+/// Line of an HTree as the parametric function `origin + t * dir`, see [`HTree::parametric_segments`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParamLine<T> {
+    /// Start of the line, reached at `t = 0`.
+    pub origin: (T, T),
+    /// Vector from the start to the stop of the line, reached at `t = 1`.
+    pub dir: (T, T),
+}
+
+pub struct HTreeIterator<T>
+where
+    T: Float,
+{
+    h_tree: HTree<T>,
+    index: usize,
+}
+
+impl<T> HTree<T>
+where
+    T: Float,
+{
+
+    /// Returns an instance of HTree up to specified order.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(10);
+    /// ```
+    pub fn new(order: usize) -> HTree<T> {
+        HTree::with_branching(order, 2)
+    }
+
+    /// Returns an instance of HTree up to specified order after checking that it can be iterated.
+    ///
+    /// Iteration only checks its grid indices with debug assertions, so trees built by this
+    /// constructor are guaranteed not to overflow in release builds as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{HTree, HTreeError};
+    /// assert!(HTree::<f32>::validated_new(10).is_ok());
+    /// assert_eq!(HTree::<f32>::validated_new(40).unwrap_err(),HTreeError::UnsupportedOrder(40));
+    /// ```
+    pub fn validated_new(order: usize) -> Result<HTree<T>, HTreeError> {
+        if !is_order_supported(order) {
+            return Err(HTreeError::UnsupportedOrder(order));
+        }
+        Ok(HTree::new(order))
+    }
+
+    /// Returns an instance of HTree up to specified order where every line has `branches` children.
+    ///
+    /// Every level splits the rectangles of the previous level into `branches` rectangles along the
+    /// direction of its lines, and every line connects the first and the last center of such a group.
+    /// The children of a line are therefore centered on `branches` equally spaced points along it,
+    /// with `branches = 2` these are its endpoints which gives the classic HTree.
+    ///
+    /// * `branches = 3` puts a third child through the middle of every line, level `l` has `3^l` lines.
+    /// * `branches = 4` puts children at the ends and at a third and two thirds of every line,
+    ///   level `l` has `4^l` lines.
+    ///
+    /// With [`Shape::Classic`] the bounding box has a height of `1/sqrt(branches)`, so every line is
+    /// `1/sqrt(branches)` times as long as the lines of the previous level.
+    /// Iteration requires `branches^(order+1)` to fit into `u32`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `branches` is smaller than 2.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::with_branching(5,3);
+    /// assert_eq!(htree.into_iter().count(),1+3+9+27+81+243);
+    /// ```
+    pub fn with_branching(order: usize, branches: u32) -> HTree<T> {
+        assert!(branches >= 2, "an HTree needs at least 2 branches, got {branches}");
+        HTree {
+            order,
+            branches,
+            shape: Shape::Classic,
+            emission_order: EmissionOrder::AsBuilt,
+            size: None,
+            origin: (T::zero(), T::zero()),
+            flipped: false,
+            length_variation: None,
+            _marker: PhantomData {},
+        }
+    }
+
+    /// Returns an instance of HTree up to specified order drawn into the box `[0, width] x [0, height]`.
+    ///
+    /// Both axes are scaled independently, so the [`Shape`] no longer influences the coordinates
+    /// and lines of odd levels are `height/width` times as long as with a square box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::with_size(0,800.0,300.0);
+    /// assert_eq!(htree.into_iter().next(),Some(((200.0,150.0),(600.0,150.0))));
+    /// ```
+    pub fn with_size(order: usize, width: T, height: T) -> HTree<T> {
+        HTree {
+            size: Some((width, height)),
+            ..HTree::new(order)
+        }
+    }
+
+    /// Returns an instance of HTree drawn into the box `[0, 1] x [0, aspect]` whose shortest lines
+    /// are as short as possible while still at least `min_len` long.
+    ///
+    /// The order stops at [`MAX_SUPPORTED_ORDER`], and is 0 if even the first line is shorter than `min_len`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_len` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::until_length(0.1,0.5f64.sqrt());
+    /// assert_eq!(htree.order(),4);
+    /// ```
+    pub fn until_length(min_len: T, aspect: T) -> HTree<T> {
+        assert!(min_len > T::zero(), "min_len must be positive");
+        let mut h_tree = HTree::with_size(0, T::one(), aspect);
+        while h_tree.order < MAX_SUPPORTED_ORDER && h_tree.segment_length(h_tree.order as u32 + 1) >= min_len {
+            h_tree.order += 1;
+        }
+        h_tree
+    }
+
+    /// Returns the highest order whose HTree drawn into the box `[0, 1] x [0, aspect]` has a total
+    /// length of at most `max_len`.
+    ///
+    /// Total lengths are computed in closed form, see [`HTree::total_length`], without generating any
+    /// line. The order stops at [`MAX_SUPPORTED_ORDER`], and is 0 if even the first line is longer than `max_len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let order=HTree::<f64>::order_for_max_length(20.0,0.5f64.sqrt());
+    /// assert_eq!(order,7);
+    /// ```
+    pub fn order_for_max_length(max_len: T, aspect: T) -> usize {
+        let mut order = 0;
+        while order < MAX_SUPPORTED_ORDER && HTree::with_size(order + 1, T::one(), aspect).total_length() <= max_len {
+            order += 1;
+        }
+        order
+    }
+
+    /// Returns an instance of HTree whose horizontal lines reach up to level `x_order` and whose
+    /// vertical lines reach up to level `y_order`.
+    ///
+    /// Horizontal lines make up the even levels and vertical lines the odd ones. Since the children
+    /// of every line are perpendicular to it, a level can only exist if the level before it does,
+    /// so the lines of one direction can recurse at most one level deeper than the others. The
+    /// HTree has the order `min(x_order, y_order)`, plus one level if the next level runs in the
+    /// direction of the larger order. An order which is larger by one but whose next level runs in
+    /// the other direction adds no lines, in the same way `y_order` is irrelevant for level 0.
+    ///
+    /// The result is an ordinary HTree of that order and `next` computes its lines unchanged: every
+    /// level still splits the rectangles of the level before it along alternating axes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the orders differ by more than 1, the deeper direction could not reach its order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// // level 4 is horizontal
+    /// assert_eq!(HTree::<f32>::with_axis_orders(4,3).order(),4);
+    /// // level 4 is not vertical, so vertical lines end at level 3 as well
+    /// assert_eq!(HTree::<f32>::with_axis_orders(3,4).order(),3);
+    /// assert_eq!(HTree::<f32>::with_axis_orders(7,7).order(),7);
+    /// ```
+    pub fn with_axis_orders(x_order: usize, y_order: usize) -> HTree<T> {
+        assert!(
+            x_order.abs_diff(y_order) <= 1,
+            "lines of one direction recurse at most one level deeper, got x_order {x_order} and y_order {y_order}"
+        );
+        let order = x_order.min(y_order);
+        // even levels are horizontal
+        let next_order = if (order + 1).is_multiple_of(2) { x_order } else { y_order };
+        HTree::new(if next_order > order { order + 1 } else { order })
+    }
+
+    /// Returns an instance of HTree up to specified order whose lines are scaled randomly about
+    /// their midpoint by factors in `[1 - variation, 1 + variation]`.
+    ///
+    /// Factors are pseudo random but fully determined by `seed` and the id of the line, see
+    /// [`HTree::iter_with_id`], so the same seed reproduces the same geometry. Every line stays
+    /// centered on the point of its varied parent it branches off, which moves with the scaling of
+    /// the parent, so the lines remain connected. Closed form statistics like [`HTree::growth_report`]
+    /// and [`HTree::total_length`] describe the HTree without variation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `variation` does not lie in `[0, 1)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let a:HTree<f64>=HTree::with_length_variation(6,0.2,7);
+    /// let b:HTree<f64>=HTree::with_length_variation(6,0.2,7);
+    /// assert_eq!(a.into_iter().collect::<Vec<_>>(),b.into_iter().collect::<Vec<_>>());
+    /// assert!(a.is_connected());
+    /// ```
+    pub fn with_length_variation(order: usize, variation: T, seed: u64) -> HTree<T> {
+        assert!(
+            variation >= T::zero() && variation < T::one(),
+            "variation must lie in [0, 1)"
+        );
+        HTree {
+            length_variation: Some((variation, seed)),
+            ..HTree::new(order)
+        }
+    }
+
+    /// Returns the order up to which the HTree is iterated.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Returns the number of children of every line.
+    pub fn branches(&self) -> u32 {
+        self.branches
+    }
+
+    /// Returns the HTree drawn into the bounding box of the given shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{HTree, Shape};
+    /// let htree:HTree<f32>=HTree::new(10).with_shape(Shape::Square);
+    /// assert_eq!(htree.shape(),Shape::Square);
+    /// ```
+    pub fn with_shape(self, shape: Shape) -> HTree<T> {
+        HTree { shape, ..self }
+    }
+
+    /// Returns the shape of the bounding box of the HTree.
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    /// Returns the HTree yielding the lines within each level in the given order.
+    ///
+    /// Levels are always yielded from the coarsest to the finest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{EmissionOrder, HTree};
+    /// let htree:HTree<f32>=HTree::new(10).with_emission_order(EmissionOrder::RowMajor);
+    /// assert_eq!(htree.emission_order(),EmissionOrder::RowMajor);
+    /// ```
+    pub fn with_emission_order(self, emission_order: EmissionOrder) -> HTree<T> {
+        HTree {
+            emission_order,
+            ..self
+        }
+    }
+
+    /// Returns the order in which the lines within each level are yielded.
+    pub fn emission_order(&self) -> EmissionOrder {
+        self.emission_order
+    }
+
+    /// Returns the HTree scaled uniformly into the rectangle from `min` to `max` and centered within it.
+    ///
+    /// The bounding box keeps its current aspect, so it touches either both sides or both the top
+    /// and the bottom of the rectangle. Use [`HTree::with_stretched_bounds`] to fill the rectangle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{HTree, Shape};
+    /// let htree:HTree<f64>=HTree::new(10).with_shape(Shape::Square).with_bounds((0.0,0.0),(4.0,2.0));
+    /// assert_eq!(htree.segment_at(0),Some(((1.5,1.0),(2.5,1.0))));
+    /// ```
+    pub fn with_bounds(self, min: (T, T), max: (T, T)) -> HTree<T> {
+        let (width, height) = self.extent();
+        let (bounds_width, bounds_height) = (max.0 - min.0, max.1 - min.1);
+        let scale = (bounds_width / width).min(bounds_height / height);
+        let (width, height) = (width * scale, height * scale);
+        HTree {
+            size: Some((width, height)),
+            origin: (
+                min.0 + (bounds_width - width) * half(),
+                min.1 + (bounds_height - height) * half(),
+            ),
+            ..self
+        }
+    }
+
+    /// Returns the HTree stretched onto the rectangle from `min` to `max`.
+    ///
+    /// Unlike [`HTree::with_bounds`] the bounding box becomes the rectangle itself, distorting the
+    /// HTree unless the rectangle has the aspect of its bounding box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(10).with_stretched_bounds((-2.0,1.0),(2.0,3.0));
+    /// assert_eq!(htree.segment_at(0),Some(((-1.0,2.0),(1.0,2.0))));
+    /// ```
+    pub fn with_stretched_bounds(self, min: (T, T), max: (T, T)) -> HTree<T> {
+        HTree {
+            size: Some((max.0 - min.0, max.1 - min.1)),
+            origin: min,
+            ..self
+        }
+    }
+
+    /// Returns the HTree mirrored top to bottom within its bounding box, for coordinate systems
+    /// whose y axis points up.
+    ///
+    /// The lines as a whole are symmetric about the horizontal center line, but the lines within a
+    /// level are yielded from the bottom instead of the top.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(1).with_flipped_y();
+    /// let (start,_)=htree.segment_at(1).unwrap();
+    /// assert!((start.1-0.75/2f64.sqrt()).abs()<1e-12);
+    /// ```
+    pub fn with_flipped_y(self) -> HTree<T> {
+        HTree {
+            flipped: !self.flipped,
+            ..self
+        }
+    }
+
+    /// Returns the number of lines per level, their lengths and the cumulative length of the HTree.
+    ///
+    /// Every level doubles the number of lines while shrinking them by a factor of `1/sqrt(2)`
+    /// (on average for [`Shape::Square`]), which makes the H Tree space filling with a fractal dimension of 2.
+    /// The same holds with `branches` children per line and a factor of `1/sqrt(branches)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(4);
+    /// let report=htree.growth_report();
+    /// assert_eq!(report.segments_per_level,vec![1,2,4,8,16]);
+    /// assert!((report.fractal_dimension-2.0).abs()<1e-12);
+    /// ```
+    pub fn growth_report(&self) -> GrowthReport<T> {
+        let segments_per_level: Vec<usize> = (0..=self.order as u32).map(|level| (self.branches as usize).pow(level)).collect();
+        let segment_lengths: Vec<T> = (0..=self.order as u32).map(|level| self.segment_length(level)).collect();
+        let cumulative_lengths = segments_per_level
+            .iter()
+            .zip(segment_lengths.iter())
+            .scan(T::zero(), |total, (&count, &length)| {
+                *total = *total + T::from(count).unwrap() * length;
+                Some(*total)
+            })
+            .collect();
+        let scaling_ratio = self.classic_aspect();
+        let fractal_dimension = T::from(self.branches).unwrap().ln() / scaling_ratio.recip().ln();
+        GrowthReport {
+            segments_per_level,
+            segment_lengths,
+            cumulative_lengths,
+            scaling_ratio,
+            fractal_dimension,
+        }
+    }
+
+    /// Returns an iterator over the lines of the HTree stroked as quads of constant width.
+    ///
+    /// Every quad is made of the two sides of a line offset perpendicular by `width/2`,
+    /// given as `[start+offset, stop+offset, stop-offset, start-offset]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(4);
+    /// for [a,b,c,d] in htree.ribbons(0.01){
+    ///     println!("quad {a:?} {b:?} {c:?} {d:?}");
+    /// }
+    /// ```
+    pub fn ribbons(&self, width: T) -> impl Iterator<Item = [(T, T); 4]> {
+        let half_width = width / T::from(2).unwrap();
+        self.into_iter().map(move |(start, stop)| {
+            let (dx, dy) = (stop.0 - start.0, stop.1 - start.1);
+            let length = dx.hypot(dy);
+            let offset = (-dy / length * half_width, dx / length * half_width);
+            [
+                (start.0 + offset.0, start.1 + offset.1),
+                (stop.0 + offset.0, stop.1 + offset.1),
+                (stop.0 - offset.0, stop.1 - offset.1),
+                (start.0 - offset.0, start.1 - offset.1),
+            ]
+        })
+    }
+
+    /// Returns the number of rectangles `(horizontal, vertical)` the bounding box is split into at `level`.
+    ///
+    /// The centers of these rectangles are the endpoints of the lines of that level.
+    /// Both counts are equal on odd levels while there are [`HTree::branches`] times as many horizontal
+    /// rectangles on even levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(4);
+    /// assert_eq!(htree.debug_level_grid(3),(4,4));
+    /// ```
+    pub fn debug_level_grid(&self, level: usize) -> (u32, u32) {
+        self.grid_dimensions(level as u32)
+    }
+
+    /// Returns an iterator over the lines of the HTree which intersect the rectangle spanned by `min` and `max`.
+    ///
+    /// Instead of testing every line, only the rows and columns of each level which can reach the
+    /// rectangle are visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(10);
+    /// let visible=htree.segments_in_rect((0.0,0.0),(0.1,0.1)).count();
+    /// assert!(visible<htree.into_iter().count());
+    /// ```
+    pub fn segments_in_rect(&self, min: (T, T), max: (T, T)) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let h_tree = *self;
+        let half_stroke = T::from(self.branches - 1).unwrap() / T::from(2).unwrap();
+        let per_group = self.branches;
+        let (width, height) = self.extent();
+        // the rectangle within the bounding box at the origin, before flipping
+        let (corner, opposite) = (self.unplace(min), self.unplace(max));
+        let (grid_min, grid_max) = (
+            (corner.0.min(opposite.0), corner.1.min(opposite.1)),
+            (corner.0.max(opposite.0), corner.1.max(opposite.1)),
+        );
+        // varied lines move by at most their variation times the sum of half the lengths of all levels
+        let margin = self.length_variation.map_or(T::zero(), |(variation, _)| variation * (width + height));
+        let (x_min, x_max) = ((grid_min.0 - margin) / width, (grid_max.0 + margin) / width);
+        let (y_min, y_max) = ((grid_min.1 - margin) / height, (grid_max.1 + margin) / height);
+        (0..=self.order as u32)
+            .flat_map(move |order_index| {
+                let (num_horizontal_rectangles, num_vertical_rectangles) = h_tree.grid_dimensions(order_index);
+                let horizontal_stroke = half_stroke / T::from(num_horizontal_rectangles).unwrap();
+                let vertical_stroke = half_stroke / T::from(num_vertical_rectangles).unwrap();
+                // lines are grouped into rows (horizontal) or columns (vertical) of `per_line` lines each
+                let (lines, along, per_line) = if order_index % 2 == 1 {
+                    (
+                        index_range(x_min, x_max, num_horizontal_rectangles),
+                        index_range(y_min - vertical_stroke, y_max + vertical_stroke, num_vertical_rectangles / per_group),
+                        num_vertical_rectangles / per_group,
+                    )
+                } else {
+                    (
+                        index_range(y_min, y_max, num_vertical_rectangles),
+                        index_range(x_min - horizontal_stroke, x_max + horizontal_stroke, num_horizontal_rectangles / per_group),
+                        num_horizontal_rectangles / per_group,
+                    )
+                };
+                lines.flat_map(move |line| {
+                    along
+                        .clone()
+                        .map(move |position| h_tree.segment(order_index, line * per_line + position))
+                })
+            })
+            .filter(move |segment| segment_intersects_rect(segment, min, max))
+    }
+
+    /// Returns the lines of the HTree joined into connected polylines.
+    ///
+    /// Lines are split where they meet the endpoints of other lines, and the pieces are chained
+    /// through these junctions preferring to continue straight.
+    /// Every piece of every line is part of exactly one polyline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(3);
+    /// for polyline in htree.polylines(){
+    ///     assert!(polyline.len()>=2);
+    /// }
+    /// ```
+    pub fn polylines(&self) -> Vec<Vec<(T, T)>> {
+        let graph = graph::Graph::new(self);
+        graph
+            .polylines()
+            .into_iter()
+            .map(|polyline| polyline.into_iter().map(|vertex| graph.vertices[vertex]).collect())
+            .collect()
+    }
+
+    /// Returns an iterator over the lines of the HTree with start and stop swapped.
+    ///
+    /// Lines are yielded in the same order as by [`HTree::into_iter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(3);
+    /// let (start,stop)=htree.into_iter().next().unwrap();
+    /// assert_eq!(htree.reversed_segments().next(),Some((stop,start)));
+    /// ```
+    pub fn reversed_segments(&self) -> impl Iterator<Item = ((T, T), (T, T))> {
+        self.into_iter().map(|(start, stop)| (stop, start))
+    }
+
+    /// Returns the tips of the HTree, the endpoints which touch no other line.
+    ///
+    /// These are the vertices of degree one in the vertex graph of the HTree, which are exactly
+    /// the endpoints of the lines of the deepest level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(3);
+    /// assert_eq!(htree.leaf_points().len(),16);
+    /// ```
+    pub fn leaf_points(&self) -> Vec<(T, T)> {
+        let graph = graph::Graph::new(self);
+        graph
+            .adjacency()
+            .iter()
+            .zip(graph.vertices.iter())
+            .filter(|(edges, _)| edges.len() == 1)
+            .map(|(_, &vertex)| vertex)
+            .collect()
+    }
+
+    /// Returns the center and radius of the smallest circle containing all lines of the HTree.
+    ///
+    /// An HTree is point symmetric about the center of its bounding box, so the smallest enclosing
+    /// circle is centered there and its radius is the largest distance from the center to any endpoint.
+    /// Both are computed from the endpoints in two passes and are exact up to rounding of `T`.
+    ///
+    /// Lines of an HTree [with length variation](HTree::with_length_variation) are not symmetric, its
+    /// smallest enclosing circle is found with Welzl's algorithm over all endpoints instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(1);
+    /// let (center,radius)=htree.enclosing_circle();
+    /// assert!((center.0-0.5).abs()<1e-12);
+    /// assert!((radius-(0.0625f64+0.03125).sqrt()).abs()<1e-12);
+    /// ```
+    pub fn enclosing_circle(&self) -> ((T, T), T) {
+        if let Some((_, seed)) = self.length_variation {
+            let mut points: Vec<(T, T)> = self.into_iter().flat_map(|(start, stop)| [start, stop]).collect();
+            return smallest_enclosing_circle(&mut points, seed);
+        }
+        let (mut min, mut max) = ((T::infinity(), T::infinity()), (T::neg_infinity(), T::neg_infinity()));
+        for point in self.into_iter().flat_map(|(start, stop)| [start, stop]) {
+            min = (min.0.min(point.0), min.1.min(point.1));
+            max = (max.0.max(point.0), max.1.max(point.1));
+        }
+        let two = T::from(2).unwrap();
+        let center = ((min.0 + max.0) / two, (min.1 + max.1) / two);
+        let radius = self
+            .into_iter()
+            .flat_map(|(start, stop)| [start, stop])
+            .map(|point| (point.0 - center.0).hypot(point.1 - center.1))
+            .fold(T::zero(), T::max);
+        (center, radius)
+    }
+
+    /// Returns an iterator over the lines of the HTree with every endpoint displaced by up to `amplitude`.
+    ///
+    /// Displacements are pseudo random but fully determined by `seed` and the position of the endpoint,
+    /// so equal points are always displaced equally and the same seed reproduces the same output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(6);
+    /// let a:Vec<_>=htree.jittered(0.002,42).collect();
+    /// let b:Vec<_>=htree.jittered(0.002,42).collect();
+    /// assert_eq!(a,b);
+    /// ```
+    pub fn jittered(self, amplitude: T, seed: u64) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let epsilon = graph::epsilon(&self);
+        let jitter = move |point: (T, T)| {
+            let cell = (
+                (point.0 / epsilon).round().to_i64().unwrap_or(0),
+                (point.1 / epsilon).round().to_i64().unwrap_or(0),
+            );
+            let hash = split_mix(split_mix(seed ^ cell.0 as u64) ^ cell.1 as u64);
+            let radius = amplitude * T::from(unit_interval(hash)).unwrap();
+            let angle = T::from(unit_interval(split_mix(hash)) * std::f64::consts::TAU).unwrap();
+            (point.0 + radius * angle.cos(), point.1 + radius * angle.sin())
+        };
+        self.into_iter().map(move |(start, stop)| (jitter(start), jitter(stop)))
+    }
+
+    /// Returns the lines of the HTree grouped into connected components.
+    ///
+    /// Lines are connected where an endpoint of one touches another line, components are found
+    /// with a union find over the vertex graph of the HTree. Components are ordered by their first
+    /// line and keep the lines in the order yielded by [`HTree::into_iter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(5);
+    /// assert_eq!(htree.connected_components().len(),1);
+    /// ```
+    pub fn connected_components(&self) -> Vec<Vec<Line<T>>> {
+        let graph = graph::Graph::new(self);
+        let components = graph.components();
+        let mut line_components = vec![usize::MAX; graph.lines.last().map_or(0, |line| line + 1)];
+        for (&(start, _), &line) in graph.edges.iter().zip(graph.lines.iter()) {
+            line_components[line] = components[start];
+        }
+
+        let mut groups: Vec<Vec<Line<T>>> = Vec::new();
+        let mut group_of_component = std::collections::HashMap::new();
+        for (line, component) in self.into_iter().zip(line_components) {
+            let group = *group_of_component.entry(component).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(line);
+        }
+        groups
+    }
+
+    /// Returns an iterator over the lines of the HTree with all coordinates rounded to `decimals` decimal places.
+    ///
+    /// Rounding only depends on the coordinate itself, so points shared by several lines stay shared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(2);
+    /// let ((x,_),_)=htree.snapped(2).next().unwrap();
+    /// assert_eq!(x,0.25);
+    /// ```
+    pub fn snapped(&self, decimals: u32) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let factor = T::from(10).unwrap().powi(decimals as i32);
+        let snap = move |(x, y): (T, T)| ((x * factor).round() / factor, (y * factor).round() / factor);
+        self.into_iter().map(move |(start, stop)| (snap(start), snap(stop)))
+    }
+
+    /// Returns an iterator over the lines of the HTree as start point, unit direction and length.
+    ///
+    /// Lines of zero length have no direction and are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(2);
+    /// let (start,direction,length)=htree.motion_segments().next().unwrap();
+    /// assert_eq!((start.0,direction,length),(0.25,(1.0,0.0),0.5));
+    /// ```
+    pub fn motion_segments(&self) -> impl Iterator<Item = ((T, T), (T, T), T)> {
+        self.into_iter().filter_map(|(start, stop)| {
+            let (dx, dy) = (stop.0 - start.0, stop.1 - start.1);
+            let length = dx.hypot(dy);
+            if length == T::zero() {
+                return None;
+            }
+            Some((start, (dx / length, dy / length), length))
+        })
+    }
+
+    /// Returns an iterator over the lines of the HTree with the index of their parent and their own index.
+    ///
+    /// Indices count the lines in the order they are yielded by [`HTree::into_iter`]. Every line is
+    /// centered on its parent line of the previous level, only the root of level 0 has no parent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(2);
+    /// let parents:Vec<_>=htree.iter_with_parent().map(|(parent,_,_)|parent).collect();
+    /// assert_eq!(parents,vec![None,Some(0),Some(0),Some(1),Some(2),Some(1),Some(2)]);
+    /// ```
+    pub fn iter_with_parent(&self) -> impl Iterator<Item = (Option<usize>, usize, Line<T>)> {
+        let h_tree = *self;
+        self.into_iter().enumerate().map(move |(index, line)| {
+            let (order_index, iteration_index) = h_tree.locate_line(index).unwrap();
+            let parent = h_tree.parent(order_index, iteration_index);
+            (parent, index, line)
+        })
+    }
+
+    /// Returns whether all lines of the HTree form a single connected figure.
+    ///
+    /// Connectivity is checked on the vertex graph of the lines, which joins points closer than a
+    /// small fraction of the shortest line, so gaps in the computed coordinates show up as
+    /// additional components.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(8);
+    /// assert!(htree.is_connected());
+    /// ```
+    pub fn is_connected(&self) -> bool {
+        let components = graph::Graph::new(self).components();
+        components.iter().all(|&component| component == components[0])
+    }
+
+    /// Returns an iterator over the lines of the levels `first_level` up to and including `last_level`.
+    ///
+    /// Lines are yielded in the same order as by [`HTree::into_iter`], levels beyond the order of the
+    /// HTree are empty. Lines of lower levels are skipped without being computed, with
+    /// [`EmissionOrder::DepthFirst`] all other lines are still visited to find their level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(6);
+    /// assert_eq!(htree.segments_between(2,3).count(),4+8);
+    /// ```
+    pub fn segments_between(&self, first_level: usize, last_level: usize) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let h_tree = *self;
+        let levels = first_level as u32..=last_level.min(self.order) as u32;
+        let indices = if self.is_depth_first() {
+            0..self.len()
+        } else if levels.is_empty() {
+            0..0
+        } else {
+            self.first_index(*levels.start())..self.first_index(*levels.end() + 1)
+        };
+        indices.filter_map(move |index| {
+            let (order_index, iteration_index) = h_tree.locate_line(index)?;
+            levels
+                .contains(&order_index)
+                .then(|| h_tree.segment(order_index, iteration_index))
+        })
+    }
+
+    /// Returns every distinct vertex of the HTree with the number of lines meeting there.
+    ///
+    /// Lines are split where they touch other lines, so tips have a valence of 1 while the ends of
+    /// inner lines, which touch the middle of their children, have a valence of 3.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(1);
+    /// let junctions=htree.vertex_valences().iter().filter(|(_,valence)|*valence==3).count();
+    /// assert_eq!(junctions,2);
+    /// ```
+    pub fn vertex_valences(&self) -> Vec<((T, T), usize)> {
+        let graph = graph::Graph::new(self);
+        graph
+            .adjacency()
+            .iter()
+            .zip(graph.vertices.iter())
+            .map(|(edges, &vertex)| (vertex, edges.len()))
+            .collect()
+    }
+
+    /// Returns an iterator over the lines of the HTree in OpenGL normalized device coordinates.
+    ///
+    /// The bounding box is centered in `[-1, 1] x [-1, 1]` and scaled uniformly until its longer side
+    /// spans the square, so the aspect ratio is kept and the shorter side is letterboxed.
+    /// The y axis is flipped to point up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(0);
+    /// let ((x,y),_)=htree.ndc_iter().next().unwrap();
+    /// assert_eq!((x,y),(-0.5,0.0));
+    /// ```
+    pub fn ndc_iter(&self) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let two = T::from(2).unwrap();
+        let (width, height) = self.extent();
+        let scale = two / width.max(height);
+        let h_tree = *self;
+        let to_ndc = move |point: (T, T)| {
+            let (x, y) = h_tree.relative(point);
+            ((x - width / two) * scale, (height / two - y) * scale)
+        };
+        self.into_iter().map(move |(start, stop)| (to_ndc(start), to_ndc(stop)))
+    }
+
+    /// Returns the HTree drawn with box drawing characters on a grid of `cols` x `rows` characters.
+    ///
+    /// The bounding box is stretched onto the whole grid. Every line marks the cells it passes
+    /// through with the directions it leaves them in, and every cell shows the character joining
+    /// all its directions, such as `─`, `│`, `├` or `┼`. Lines are separated by `\n`. A grid
+    /// without columns is `rows` empty lines, and one without rows is the empty string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(1);
+    /// assert_eq!(htree.to_ascii(5,3)," ╷ ╷ \n ├─┤ \n ╵ ╵ ");
+    /// ```
+    pub fn to_ascii(&self, cols: u32, rows: u32) -> String {
+        const UP: u8 = 1;
+        const DOWN: u8 = 2;
+        const LEFT: u8 = 4;
+        const RIGHT: u8 = 8;
+        let (cols, rows) = (cols as usize, rows as usize);
+        if rows == 0 {
+            return String::new();
+        }
+        if cols == 0 {
+            return vec![""; rows].join("\n");
+        }
+        let mut cells = vec![0u8; cols * rows];
+        let (width, height) = self.extent();
+        let cell = |value: T, count: usize| {
+            (value * T::from(count).unwrap()).floor().to_usize().unwrap_or(0).min(count.saturating_sub(1))
+        };
+        for (start, stop) in self.into_iter() {
+            let (start, stop) = (self.relative(start), self.relative(stop));
+            let (start_col, stop_col) = (cell(start.0 / width, cols), cell(stop.0 / width, cols));
+            let (start_row, stop_row) = (cell(start.1 / height, rows), cell(stop.1 / height, rows));
+            if (stop.0 - start.0).abs() >= (stop.1 - start.1).abs() {
+                let (first, last) = (start_col.min(stop_col), start_col.max(stop_col));
+                for col in first..=last {
+                    let mut directions = 0;
+                    if col > first || first == last {
+                        directions |= LEFT;
+                    }
+                    if col < last || first == last {
+                        directions |= RIGHT;
+                    }
+                    cells[start_row * cols + col] |= directions;
+                }
+            } else {
+                let (first, last) = (start_row.min(stop_row), start_row.max(stop_row));
+                for row in first..=last {
+                    let mut directions = 0;
+                    if row > first || first == last {
+                        directions |= UP;
+                    }
+                    if row < last || first == last {
+                        directions |= DOWN;
+                    }
+                    cells[row * cols + start_col] |= directions;
+                }
+            }
+        }
+        cells
+            .chunks(cols)
+            .map(|row| row.iter().map(|&directions| box_drawing_char(directions)).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns an iterator over the lines of the HTree with their orientation.
+    ///
+    /// Lines of even levels are horizontal and lines of odd levels are vertical.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{HTree, Orientation};
+    /// let htree:HTree<f32>=HTree::new(1);
+    /// let orientations:Vec<_>=htree.iter_with_orientation().map(|(orientation,_)|orientation).collect();
+    /// assert_eq!(orientations,vec![Orientation::Horizontal,Orientation::Vertical,Orientation::Vertical]);
+    /// ```
+    pub fn iter_with_orientation(&self) -> impl Iterator<Item = (Orientation, Line<T>)> {
+        let h_tree = *self;
+        self.into_iter().enumerate().map(move |(index, line)| {
+            let (order_index, _) = h_tree.locate_line(index).unwrap();
+            let orientation = if order_index % 2 == 1 {
+                Orientation::Vertical
+            } else {
+                Orientation::Horizontal
+            };
+            (orientation, line)
+        })
+    }
+
+    /// Returns the line with index `index` in the order yielded by [`HTree::into_iter`].
+    ///
+    /// The line is computed directly from its index without iterating over the lines before it.
+    /// Returns `None` if the index lies beyond the order of the HTree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(6);
+    /// assert_eq!(htree.segment_at(40),htree.into_iter().nth(40));
+    /// assert_eq!(htree.segment_at(127),None);
+    /// ```
+    pub fn segment_at(&self, index: usize) -> Option<Line<T>> {
+        let (order_index, iteration_index) = self.locate_line(index)?;
+        Some(self.segment(order_index, iteration_index))
+    }
+
+    /// Returns an iterator over every `step`-th line of the HTree, starting with the first.
+    ///
+    /// Lines are picked with [`HTree::segment_at`], so the skipped lines are never computed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(9);
+    /// assert_eq!(htree.sampled(10).count(),103);
+    /// ```
+    pub fn sampled(&self, step: usize) -> impl Iterator<Item = Line<T>> {
+        let h_tree = *self;
+        (0..self.len())
+            .step_by(step)
+            .map(move |index| h_tree.segment_at(index).unwrap())
+    }
+
+    /// Returns an iterator over the lines with indices within `range` in the order yielded by [`HTree::into_iter`].
+    ///
+    /// Lines are computed with [`HTree::segment_at`], so disjoint windows can be iterated
+    /// independently. Indices beyond the order of the HTree are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(4);
+    /// assert_eq!(htree.index_range_iter(3..7).count(),4);
+    /// assert_eq!(htree.index_range_iter(30..40).count(),1);
+    /// ```
+    pub fn index_range_iter(&self, range: std::ops::Range<usize>) -> impl Iterator<Item = Line<T>> {
+        let h_tree = *self;
+        let end = range.end.min(self.len());
+        (range.start..end).map(move |index| h_tree.segment_at(index).unwrap())
+    }
+
+    /// Returns the vertices of the convex hull of all endpoints of the HTree in counter clockwise order.
+    ///
+    /// Counter clockwise refers to a y axis pointing up, without collinear points on the edges.
+    /// The outermost tips of the HTree lie on a rectangle, so the hull is that rectangle or, for
+    /// orders whose outermost lines do not reach the corners, close to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(1);
+    /// assert_eq!(htree.convex_hull().len(),4);
+    /// ```
+    pub fn convex_hull(&self) -> Vec<(T, T)> {
+        fn cross<T: Float>(o: (T, T), a: (T, T), b: (T, T)) -> T {
+            (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+        }
+
+        let mut points = graph::Graph::new(self).vertices;
+        points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        if points.len() < 3 {
+            return points;
+        }
+        // monotone chain, lower hull from left to right followed by upper hull from right to left
+        let mut hull: Vec<(T, T)> = Vec::with_capacity(points.len() + 1);
+        for pass in [points.clone(), points.into_iter().rev().collect()] {
+            let floor = hull.len();
+            for point in pass {
+                while hull.len() >= floor + 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= T::zero() {
+                    hull.pop();
+                }
+                hull.push(point);
+            }
+            hull.pop();
+        }
+        hull
+    }
+
+    /// Returns the number of lines of the HTree.
+    ///
+    /// The count is computed in closed form and overflows `usize` for orders well beyond
+    /// [`MAX_SUPPORTED_ORDER`], which panics in debug builds. Use [`HTree::checked_len`] if the
+    /// order is not known to be supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(3);
+    /// assert_eq!(htree.len(),15);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.first_index(self.order as u32 + 1)
+    }
+
+    /// Returns whether the HTree has no lines, which is never the case as level 0 always has one line.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends all lines of the HTree to `out` in the order yielded by [`HTree::into_iter`].
+    ///
+    /// Capacity for [`HTree::len`] lines is reserved upfront, so `out` grows at most once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(3);
+    /// let mut lines=Vec::new();
+    /// htree.extend_into(&mut lines);
+    /// htree.extend_into(&mut lines);
+    /// assert_eq!(lines.len(),30);
+    /// ```
+    pub fn extend_into(&self, out: &mut Vec<Line<T>>) {
+        out.reserve(self.len());
+        out.extend(*self);
+    }
+
+    /// Returns the points where two lines of the HTree cross through the interiors of both.
+    ///
+    /// Lines are only allowed to meet where an endpoint touches another line, so this is empty for
+    /// a correctly computed HTree and serves as a check of its arithmetic. Collinear lines which
+    /// overlap are reported by the start of the overlap. Candidates are found with
+    /// [`HTree::segments_in_rect`], so only nearby lines are compared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(6);
+    /// assert!(htree.self_intersections().is_empty());
+    /// ```
+    pub fn self_intersections(&self) -> Vec<(T, T)> {
+        let epsilon = graph::epsilon(self);
+        let mut points: Vec<(T, T)> = self
+            .into_iter()
+            .flat_map(|line| {
+                let (start, stop) = line;
+                let min = (start.0.min(stop.0), start.1.min(stop.1));
+                let max = (start.0.max(stop.0), start.1.max(stop.1));
+                self.segments_in_rect(min, max)
+                    .filter(move |&other| other != line)
+                    .filter_map(move |other| interior_crossing(line, other, epsilon))
+            })
+            .collect();
+        points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        points.dedup_by(|a, b| (a.0 - b.0).hypot(a.1 - b.1) <= epsilon);
+        points
+    }
+
+    /// Returns an iterator over the lines of the HTree with their endpoints converted to the point type `P`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{FromXY, HTree};
+    /// struct Point{x:f32,y:f32}
+    /// impl FromXY<f32> for Point{
+    ///     fn from_xy(x:f32,y:f32)->Self{
+    ///         Point{x,y}
+    ///     }
+    /// }
+    /// let htree:HTree<f32>=HTree::new(3);
+    /// let (start,_)=htree.segments_as::<Point>().next().unwrap();
+    /// assert_eq!((start.x,start.y),htree.into_iter().next().unwrap().0);
+    /// ```
+    pub fn segments_as<P: FromXY<T>>(&self) -> impl Iterator<Item = (P, P)> {
+        self.into_iter()
+            .map(|(start, stop)| (P::from_xy(start.0, start.1), P::from_xy(stop.0, stop.1)))
+    }
+
+    /// Returns statistics on plotting the HTree with a pen in the order given by `order_mode`.
+    ///
+    /// Orders of whole levels draw every line on its own, [`EmissionOrder::DepthFirst`] walks the
+    /// vertex graph of the lines depth first and lifts the pen only when backing up, while
+    /// [`EmissionOrder::Optimized`] draws the polylines of [`HTree::polylines`] choosing the
+    /// nearest next one. The emission order of the HTree itself is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{EmissionOrder, HTree};
+    /// let htree:HTree<f64>=HTree::new(6);
+    /// let naive=htree.plot_report(EmissionOrder::AsBuilt);
+    /// let optimized=htree.plot_report(EmissionOrder::Optimized);
+    /// assert!(optimized.travel_length<naive.travel_length);
+    /// ```
+    pub fn plot_report(&self, order_mode: EmissionOrder) -> PlotReport<T> {
+        let distance = |a: (T, T), b: (T, T)| (a.0 - b.0).hypot(a.1 - b.1);
+        let strokes = plot::strokes(self, order_mode);
+        let draw_length = strokes
+            .iter()
+            .flat_map(|stroke| stroke.windows(2))
+            .fold(T::zero(), |length, pair| length + distance(pair[0], pair[1]));
+        let travel_length = strokes
+            .windows(2)
+            .fold(T::zero(), |length, pair| length + distance(pair[0][pair[0].len() - 1], pair[1][0]));
+        PlotReport {
+            draw_length,
+            travel_length,
+            pen_lifts: strokes.len().saturating_sub(1),
+        }
+    }
+
+    /// Returns an iterator over the lines of the HTree tiled 2 x 2 with mirrored copies.
+    ///
+    /// The HTree is shrunk into the top left quadrant of its bounding box and reflected about the
+    /// vertical, the horizontal and both center lines into the other quadrants, so the result is
+    /// symmetric about both center lines. Yields the original quarter, then the reflections in this
+    /// order, each in the order of [`HTree::into_iter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(3);
+    /// assert_eq!(htree.quad_symmetric().count(),4*15);
+    /// ```
+    pub fn quad_symmetric(&self) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let h_tree = *self;
+        let half: T = half();
+        let (width, height) = self.extent();
+        [(false, false), (true, false), (false, true), (true, true)]
+            .into_iter()
+            .flat_map(move |(mirror_x, mirror_y)| {
+                let place = move |point: (T, T)| {
+                    let (x, y) = h_tree.relative(point);
+                    let (x, y) = (x * half, y * half);
+                    (
+                        h_tree.origin.0 + if mirror_x { width - x } else { x },
+                        h_tree.origin.1 + if mirror_y { height - y } else { y },
+                    )
+                };
+                h_tree.into_iter().map(move |(start, stop)| (place(start), place(stop)))
+            })
+    }
+
+    /// Returns the total length of all lines of the HTree.
+    ///
+    /// This is the last entry of [`GrowthReport::cumulative_lengths`], computed in closed form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(1).with_shape(htree::Shape::Square);
+    /// assert_eq!(htree.total_length(),1.5);
+    /// ```
+    pub fn total_length(&self) -> T {
+        (0..=self.order as u32).fold(T::zero(), |total, level| {
+            total + T::from(self.branches.pow(level)).unwrap() * self.segment_length(level)
+        })
+    }
+
+    /// Returns an iterator over the dashes of the lines of the HTree in a pattern of `dash` long dashes and `gap` long gaps.
+    ///
+    /// The pattern starts anew with a dash at the start of every line, and the last dash of a line
+    /// is cut short at its stop. Lines shorter than `dash` become a single dash.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dash` is not positive or `gap` is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(0);
+    /// let dashes:Vec<_>=htree.dashed(0.2,0.1).collect();
+    /// assert_eq!(dashes.len(),2);
+    /// ```
+    pub fn dashed(&self, dash: T, gap: T) -> impl Iterator<Item = ((T, T), (T, T))> {
+        assert!(dash > T::zero() && gap >= T::zero(), "dash must be positive and gap not negative");
+        self.into_iter().flat_map(move |(start, stop)| {
+            let (dx, dy) = (stop.0 - start.0, stop.1 - start.1);
+            let length = dx.hypot(dy);
+            let point = move |along: T| {
+                let t = if length > T::zero() { along / length } else { T::zero() };
+                (start.0 + dx * t, start.1 + dy * t)
+            };
+            let mut along = T::zero();
+            std::iter::from_fn(move || {
+                if along > T::zero() && along >= length {
+                    return None;
+                }
+                let end = (along + dash).min(length);
+                let piece = (point(along), point(end));
+                along = along + dash + gap;
+                Some(piece)
+            })
+        })
+    }
+
+    /// Returns the lines of the HTree nested by their branching, starting from the line of level 0.
+    ///
+    /// Every node holds a line and the nodes of the lines centered on it, ordered along the line.
+    /// Nodes of the deepest level have no children, so the nesting is [`HTree::order`] deep.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(2);
+    /// let root=htree.to_nested();
+    /// assert_eq!(root.children.len(),2);
+    /// assert_eq!(root.children[0].children[1].children.len(),0);
+    /// ```
+    pub fn to_nested(&self) -> HNode<T> {
+        self.nested(0, 0)
+    }
+
+    /// Returns an iterator over the midpoints of the lines of the HTree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(0).with_shape(htree::Shape::Square);
+    /// assert_eq!(htree.midpoints().next(),Some((0.5,0.5)));
+    /// ```
+    pub fn midpoints(&self) -> impl Iterator<Item = (T, T)> {
+        let half: T = half();
+        self.into_iter()
+            .map(move |(start, stop)| ((start.0 + stop.0) * half, (start.1 + stop.1) * half))
+    }
+
+    /// Returns an iterator over the lines of the HTree with full detail only within `focus`.
+    ///
+    /// Lines up to level `coarse_level` are always yielded, lines of deeper levels only if their
+    /// midpoint lies within the rectangle spanned by the two corners of `focus`. Lines are yielded in
+    /// the order of [`HTree::into_iter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(8);
+    /// let focused=htree.lod_iter(((0.0,0.0),(0.25,0.25)),3).count();
+    /// assert!(focused<htree.len());
+    /// ```
+    pub fn lod_iter(&self, focus: ((T, T), (T, T)), coarse_level: usize) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let h_tree = *self;
+        let half: T = half();
+        let (corner, other) = focus;
+        let min = (corner.0.min(other.0), corner.1.min(other.1));
+        let max = (corner.0.max(other.0), corner.1.max(other.1));
+        (0..self.len()).filter_map(move |index| {
+            let (order_index, iteration_index) = h_tree.locate_line(index)?;
+            let (start, stop) = h_tree.segment(order_index, iteration_index);
+            let midpoint = ((start.0 + stop.0) * half, (start.1 + stop.1) * half);
+            let inside = (min.0..=max.0).contains(&midpoint.0) && (min.1..=max.1).contains(&midpoint.1);
+            (order_index as usize <= coarse_level || inside).then_some((start, stop))
+        })
+    }
+
+    /// Returns the outer boundary of the HTree stroked with lines of width `width` as a closed polygon.
+    ///
+    /// Every line is grown by `width/2` on all sides, ends included, so the stroke has square caps
+    /// and the polygon only has axis aligned edges. The boundary of the union of these rectangles
+    /// is traced on the grid of all their edges, so the work grows with the number of distinct
+    /// coordinates rather than with the number of lines. Vertices are given counter clockwise
+    /// (with the y axis pointing up) without collinear points, the first and last vertex are not
+    /// repeated.
+    ///
+    /// Holes enclosed by the stroke are ignored. Where strokes only touch at a corner the ring runs
+    /// through that corner twice, and if the strokes do not touch at all, as with widths far below
+    /// the line spacing of [`Shape::Square`], only the ring around the part containing the lowest
+    /// point is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(0);
+    /// assert_eq!(htree.outline(0.5).len(),4);
+    /// ```
+    pub fn outline(&self, width: T) -> Vec<(T, T)> {
+        assert!(width > T::zero(), "width must be positive");
+        let epsilon = graph::epsilon(self).min(width / T::from(1000).unwrap());
+        outline::outer_ring(&self.stroke_rectangles(width), epsilon)
+    }
+
+    /// Returns the number of bytes taken by all lines of an HTree of order `order` collected into a `Vec`.
+    ///
+    /// This is the number of lines times the size of a line, ignoring the `Vec` itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// assert_eq!(HTree::<f32>::memory_estimate(3),15*16);
+    /// ```
+    pub fn memory_estimate(order: usize) -> usize {
+        HTree::<T>::new(order).len() * std::mem::size_of::<Line<T>>()
+    }
+
+    /// Returns an iterator over the lines of the HTree together with a square pad on every tip.
+    ///
+    /// Pads are centered on the [`HTree::leaf_points`] with sides of length `pad_size`, every pad is
+    /// given by its corners `(min, max)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(3);
+    /// let (lines,pads)=htree.with_pads(0.01);
+    /// assert_eq!((lines.count(),pads.len()),(15,16));
+    /// ```
+    pub fn with_pads(&self, pad_size: T) -> (impl Iterator<Item = Line<T>>, Vec<Line<T>>) {
+        let half_size = pad_size * half();
+        let pads = self
+            .leaf_points()
+            .into_iter()
+            .map(|(x, y)| ((x - half_size, y - half_size), (x + half_size, y + half_size)))
+            .collect();
+        (self.into_iter(), pads)
+    }
+
+    /// Returns the number of lines of the HTree, or `None` if it does not fit into `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// assert_eq!(HTree::<f32>::new(3).checked_len(),Some(15));
+    /// assert_eq!(HTree::<f32>::new(64).checked_len(),None);
+    /// ```
+    pub fn checked_len(&self) -> Option<usize> {
+        let branches = self.branches as usize;
+        let (mut total, mut count) = (0usize, 1usize);
+        for level in 0..=self.order {
+            total = total.checked_add(count)?;
+            if level < self.order {
+                count = count.checked_mul(branches)?;
+            }
+        }
+        Some(total)
+    }
+
+    /// Draws the lines of the HTree into a monochrome bitmap of `width` x `height` pixels.
+    ///
+    /// Pixels are stored row by row with one bit each, the most significant bit of a byte being the
+    /// leftmost pixel, and rows are not padded. Every pixel crossed by a line is set with Bresenham's
+    /// algorithm, other bits are left untouched. The HTree is scaled as large as possible without
+    /// distorting it and centered within the bitmap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` holds fewer than `width*height` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(4);
+    /// let mut framebuffer=[0u8;128*64/8];
+    /// htree.rasterize_1bpp(&mut framebuffer,128,64);
+    /// assert!(framebuffer.iter().any(|&byte|byte!=0));
+    /// ```
+    pub fn rasterize_1bpp(&self, buf: &mut [u8], width: u32, height: u32) {
+        let bits = width as usize * height as usize;
+        assert!(
+            buf.len() * 8 >= bits,
+            "buffer of {} bytes is too small for {width}x{height} pixels, {} bytes are needed",
+            buf.len(),
+            bits.div_ceil(8)
+        );
+        if bits == 0 {
+            return;
+        }
+        self.rasterize(width, height, |index| buf[index / 8] |= 0x80 >> (index % 8));
+    }
+
+    /// Returns for every pixel of a `width` x `height` image the number of lines passing through it.
+    ///
+    /// Lines are rasterized like in [`HTree::rasterize_1bpp`] and the counts are stored row by row.
+    /// Every line contributes to at least one pixel, so the counts add up to at least [`HTree::len`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(5);
+    /// let density=htree.density_grid(32,23);
+    /// assert_eq!(density.len(),32*23);
+    /// assert!(density.iter().sum::<u32>() as usize>=htree.len());
+    /// ```
+    pub fn density_grid(&self, width: u32, height: u32) -> Vec<u32> {
+        let mut density = vec![0; width as usize * height as usize];
+        if !density.is_empty() {
+            self.rasterize(width, height, |index| density[index] += 1);
+        }
+        density
+    }
+
+    /// Returns an iterator over the lines of a single level, in the order yielded by [`HTree::into_iter`].
+    ///
+    /// Levels beyond the order of the HTree are empty, see [`HTree::segments_between`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(6);
+    /// assert_eq!(htree.level(4).count(),16);
+    /// ```
+    pub fn level(&self, level: usize) -> impl Iterator<Item = ((T, T), (T, T))> {
+        self.segments_between(level, level)
+    }
+
+    /// Returns the lines of every level computed once and stored for repeated access.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(6);
+    /// let cache=htree.level_cache();
+    /// assert_eq!(cache.level(4).len(),16);
+    /// ```
+    pub fn level_cache(&self) -> LevelCache<T> {
+        let mut levels: Vec<Vec<Line<T>>> = (0..=self.order as u32)
+            .map(|level| Vec::with_capacity(self.branches.pow(level) as usize))
+            .collect();
+        for index in 0..self.len() {
+            let (order_index, iteration_index) = self.locate_line(index).unwrap();
+            levels[order_index as usize].push(self.segment(order_index, iteration_index));
+        }
+        LevelCache { levels }
+    }
+
+    /// Returns the line in column `col` and row `row` of the grid the lines of `level` are arranged in.
+    ///
+    /// Lines of even levels form rows of [`HTree::branches`] times fewer columns than the grid of
+    /// [`HTree::debug_level_grid`], lines of odd levels columns of that many times fewer rows.
+    /// Returns `None` for cells outside that grid and levels beyond the order of the HTree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(2);
+    /// assert_eq!(htree.segment_at_cell(1,1,0),htree.segment_at(2));
+    /// assert_eq!(htree.segment_at_cell(1,0,1),None);
+    /// ```
+    pub fn segment_at_cell(&self, level: usize, col: u32, row: u32) -> Option<((T, T), (T, T))> {
+        if level > self.order {
+            return None;
+        }
+        let order_index = level as u32;
+        let (rows, columns) = self.line_grid(order_index);
+        if col >= columns || row >= rows {
+            return None;
+        }
+        let iteration_index = if order_index % 2 == 1 {
+            col * rows + row
+        } else {
+            row * columns + col
+        };
+        Some(self.segment(order_index, iteration_index))
+    }
+
+    /// Returns the indices of the lines next to the line with index `index` in the grid of its level.
+    ///
+    /// Neighbours are given as `[up, down, left, right]`, where up is the row of smaller y, using the
+    /// grid of [`HTree::segment_at_cell`]. Neighbours beyond the edge of the grid, and all neighbours
+    /// of indices beyond the order of the HTree, are `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(2);
+    /// assert_eq!(htree.segment_neighbors(1),[None,None,None,Some(2)]);
+    /// ```
+    pub fn segment_neighbors(&self, index: usize) -> [Option<usize>; 4] {
+        let Some((order_index, iteration_index)) = self.locate_line(index) else {
+            return [None; 4];
+        };
+        let (rows, columns) = self.line_grid(order_index);
+        let (row, column) = if order_index % 2 == 1 {
+            (iteration_index % rows, iteration_index / rows)
+        } else {
+            (iteration_index / columns, iteration_index % columns)
+        };
+        [(0, -1), (0, 1), (-1, 0), (1, 0)].map(|(dx, dy): (i64, i64)| {
+            let (column, row) = (column as i64 + dx, row as i64 + dy);
+            if column < 0 || row < 0 || column >= columns as i64 || row >= rows as i64 {
+                return None;
+            }
+            let (column, row) = (column as u32, row as u32);
+            let iteration_index = if order_index % 2 == 1 {
+                column * rows + row
+            } else {
+                row * columns + column
+            };
+            Some(self.index_of(order_index, iteration_index))
+        })
+    }
+
+    /// Returns a function computing the line with a given index in the order yielded by [`HTree::into_iter`].
+    ///
+    /// The function captures a copy of the HTree and computes lines like [`HTree::segment_at`].
+    ///
+    /// # Panics
+    ///
+    /// The returned function panics for indices beyond the order of the HTree, use
+    /// [`HTree::segment_at`] to check them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(8);
+    /// let segment=htree.segment_fn();
+    /// assert_eq!(Some(segment(300)),htree.segment_at(300));
+    /// ```
+    pub fn segment_fn(&self) -> impl Fn(usize) -> ((T, T), (T, T)) {
+        let h_tree = *self;
+        move |index| {
+            h_tree
+                .segment_at(index)
+                .unwrap_or_else(|| panic!("index {index} is beyond the {} lines of the HTree", h_tree.len()))
+        }
+    }
+
+    /// Returns an iterator over the lines of the HTree as their start and the offset from start to stop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(0);
+    /// assert_eq!(htree.relative_segments().next(),Some(((0.25,0.35355338),(0.5,0.0))));
+    /// ```
+    pub fn relative_segments(&self) -> impl Iterator<Item = ((T, T), (T, T))> {
+        self.into_iter().map(|(start, stop)| (start, (stop.0 - start.0, stop.1 - start.1)))
+    }
+
+    /// Returns an iterator over the lines of the HTree as pen moves relative to the previous position.
+    ///
+    /// Every item is the offset from the stop of the previous line (the origin for the first line)
+    /// to the start of the line followed by the offset from its start to its stop, as taken by
+    /// plotters addressed in relative coordinates. Summing up the offsets reconstructs the lines
+    /// up to rounding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(0);
+    /// assert_eq!(htree.chained_segments().next(),Some(((0.25,0.35355338),(0.5,0.0))));
+    /// ```
+    pub fn chained_segments(&self) -> impl Iterator<Item = ((T, T), (T, T))> {
+        self.into_iter().scan((T::zero(), T::zero()), |position, (start, stop)| {
+            let travel = (start.0 - position.0, start.1 - position.1);
+            *position = stop;
+            Some((travel, (stop.0 - start.0, stop.1 - start.1)))
+        })
+    }
+
+    /// Returns an iterator over the lines of the HTree in pixels of a print of `width_mm` x `height_mm`
+    /// millimeters at `dpi` dots per inch.
+    ///
+    /// The print is `width_mm / 25.4 * dpi` x `height_mm / 25.4 * dpi` pixels large, the bounding box
+    /// is scaled uniformly and centered within it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(0);
+    /// let ((x,y),_)=htree.fit_to_print(254.0,254.0,100.0).next().unwrap();
+    /// assert!((x-250.0).abs()<1e-9 && (y-500.0).abs()<1e-9);
+    /// ```
+    pub fn fit_to_print(&self, width_mm: f64, height_mm: f64, dpi: f64) -> impl Iterator<Item = ((f64, f64), (f64, f64))> {
+        const MM_PER_INCH: f64 = 25.4;
+        let (width, height) = (width_mm / MM_PER_INCH * dpi, height_mm / MM_PER_INCH * dpi);
+        let (extent_width, extent_height) = self.extent();
+        let (extent_width, extent_height) = (extent_width.to_f64().unwrap(), extent_height.to_f64().unwrap());
+        let scale = (width / extent_width).min(height / extent_height);
+        let offset = ((width - scale * extent_width) / 2.0, (height - scale * extent_height) / 2.0);
+        let h_tree = *self;
+        let pixel = move |point: (T, T)| {
+            let (x, y) = h_tree.relative(point);
+            (offset.0 + x.to_f64().unwrap() * scale, offset.1 + y.to_f64().unwrap() * scale)
+        };
+        self.into_iter().map(move |(start, stop)| (pixel(start), pixel(stop)))
+    }
+
+    /// Returns an iterator over the lines of the HTree continuing at the position stored in `checkpoint`.
+    ///
+    /// The checkpoint only stores the order, the HTree has to be configured like the one the checkpoint
+    /// was taken from to yield the same remaining lines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the checkpoint was taken from an HTree of a different order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(3);
+    /// let mut lines=htree.into_iter();
+    /// lines.nth(9);
+    /// let checkpoint=lines.checkpoint();
+    /// assert_eq!(htree.resume_from(checkpoint).count(),5);
+    /// ```
+    pub fn resume_from(&self, checkpoint: IteratorCheckpoint) -> HTreeIterator<T> {
+        assert_eq!(
+            checkpoint.order, self.order,
+            "checkpoint of an HTree of order {} cannot resume an HTree of order {}",
+            checkpoint.order, self.order
+        );
+        HTreeIterator {
+            h_tree: *self,
+            index: checkpoint.index,
+        }
+    }
+
+    /// Returns the dual graph of the grid of rectangles at `level`.
+    ///
+    /// Nodes are the centers of the rectangles of [`HTree::debug_level_grid`] row by row, which are
+    /// also the endpoints of the lines of that level. Links connect every rectangle to the rectangle
+    /// to its right and to the one below it, each link is given once with the smaller node first.
+    /// Levels beyond the order of the HTree give an empty graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(2);
+    /// let (centers,links)=htree.dual_graph(0);
+    /// assert_eq!(centers.len(),2);
+    /// assert_eq!(links,vec![(0,1)]);
+    /// ```
+    pub fn dual_graph(&self, level: usize) -> DualGraph<T> {
+        if level > self.order {
+            return (Vec::new(), Vec::new());
+        }
+        let (num_horizontal_rectangles, num_vertical_rectangles) = self.grid_dimensions(level as u32);
+        let (columns, rows) = (num_horizontal_rectangles as usize, num_vertical_rectangles as usize);
+        let (width, height) = self.extent();
+        let (cell_width, cell_height) = (width / T::from(columns).unwrap(), height / T::from(rows).unwrap());
+        let mut centers = Vec::with_capacity(columns * rows);
+        let mut links = Vec::with_capacity(2 * columns * rows);
+        for row in 0..rows {
+            for column in 0..columns {
+                let node = row * columns + column;
+                centers.push(self.place((
+                    (T::from(column).unwrap() + half()) * cell_width,
+                    (T::from(row).unwrap() + half()) * cell_height,
+                )));
+                if column + 1 < columns {
+                    links.push((node, node + 1));
+                }
+                if row + 1 < rows {
+                    links.push((node, node + columns));
+                }
+            }
+        }
+        (centers, links)
+    }
+
+    /// Returns how often two consecutive lines do not touch when yielded in the order `mode`.
+    ///
+    /// Lines touch when one of them ends on the other within a thousandth of the shortest line.
+    /// A pen can move from a line to a touching one along the drawing, every other move lifts it.
+    /// Lines within a level never touch, so orders of whole levels lift the pen between almost all
+    /// lines while the depth first orders only lift it when moving on to a sibling. The emission
+    /// order of the HTree itself is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{EmissionOrder, HTree};
+    /// let htree:HTree<f64>=HTree::new(6);
+    /// assert!(htree.pen_up_count(EmissionOrder::DepthFirst)<htree.pen_up_count(EmissionOrder::AsBuilt));
+    /// ```
+    pub fn pen_up_count(&self, mode: EmissionOrder) -> usize {
+        let epsilon = graph::epsilon(self);
+        let mut lines = self.with_emission_order(mode).into_iter();
+        let Some(mut previous) = lines.next() else {
+            return 0;
+        };
+        let mut count = 0;
+        for line in lines {
+            let ((start, stop), (other_start, other_stop)) = (line, previous);
+            let min = (other_start.0.min(other_stop.0) - epsilon, other_start.1.min(other_stop.1) - epsilon);
+            let max = (other_start.0.max(other_stop.0) + epsilon, other_start.1.max(other_stop.1) + epsilon);
+            if !segment_intersects_rect(&(start, stop), min, max) {
+                count += 1;
+            }
+            previous = line;
+        }
+        count
+    }
+
+    /// Returns the lines of the HTree as `[x0, y0, x1, y1]` records of `f64` in iteration order.
+    ///
+    /// A `Vec` of arrays is a single contiguous allocation of `4 * len()` values without padding, so
+    /// `as_ptr()` can be handed over as a `len() x 4` row major array, e.g. to numpy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(0);
+    /// assert_eq!(htree.as_f64_quads(),vec![[0.25,0.3535533845424652,0.75,0.3535533845424652]]);
+    /// ```
+    pub fn as_f64_quads(&self) -> Vec<[f64; 4]> {
+        let value = |value: T| value.to_f64().unwrap();
+        self.into_iter()
+            .map(|(start, stop)| [value(start.0), value(start.1), value(stop.0), value(stop.1)])
+            .collect()
+    }
+
+    /// Returns an iterator over the links of [`HTree::dual_graph`] at `level` which no line of that
+    /// level runs along.
+    ///
+    /// Lines of a level connect the centers of [`HTree::branches`] neighbouring rectangles of its grid,
+    /// the remaining links between neighbouring centers fill the gaps between the arms of the HTree.
+    /// They only share endpoints with the lines of the level. Levels beyond the order of the HTree
+    /// have no links.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(3);
+    /// // the 4 x 2 grid of level 2 has 10 links, 4 of them are covered by its lines
+    /// assert_eq!(htree.complement_grid(2).count(),6);
+    /// ```
+    pub fn complement_grid(&self, level: usize) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let (centers, links) = self.dual_graph(level);
+        let columns = self.grid_dimensions(level.min(self.order) as u32).0 as usize;
+        let last = self.branches as usize - 1;
+        let vertical = level % 2 == 1;
+        links
+            .into_iter()
+            .filter(move |&(a, b)| {
+                let along = (b != a + 1) == vertical;
+                let position = if vertical { a / columns } else { a % columns };
+                !along || position % (last + 1) == last
+            })
+            .map(move |(a, b)| (centers[a], centers[b]))
+    }
+
+    /// Returns an iterator over the lines of the HTree together with an ID which depends only on
+    /// their level and their position within it.
+    ///
+    /// The ID of a line is its index in [`EmissionOrder::AsBuilt`], so the lines keep their IDs when
+    /// the order or the emission order of the HTree changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{EmissionOrder, HTree};
+    /// let htree:HTree<f32>=HTree::new(2).with_emission_order(EmissionOrder::DepthFirst);
+    /// let ids:Vec<u64>=htree.iter_with_id().map(|(id,_)|id).collect();
+    /// assert_eq!(ids,vec![0,1,3,5,2,4,6]);
+    /// ```
+    pub fn iter_with_id(&self) -> impl Iterator<Item = (u64, Line<T>)> {
+        let h_tree = *self;
+        (0..self.len()).map(move |index| {
+            let (order_index, iteration_index) = h_tree.locate_line(index).unwrap();
+            let id = h_tree.first_index(order_index) as u64 + iteration_index as u64;
+            (id, h_tree.segment(order_index, iteration_index))
+        })
+    }
+
+    /// Returns an SVG document drawing the HTree in black into its bounding box.
+    ///
+    /// All lines form a single path stroked `stroke_width` wide. With a positive `corner_radius`
+    /// every T junction is rounded off: the line ending at a junction stops `corner_radius` before
+    /// it and two arcs lead onto both halves of the perpendicular line. The radius is limited to half
+    /// the length of that line, crossings in the middle of lines with more than 2 branches stay sharp.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(1);
+    /// assert!(htree.to_svg(0.01,0.05).contains('A'));
+    /// assert!(!htree.to_svg(0.01,0.0).contains('A'));
+    /// ```
+    pub fn to_svg(&self, stroke_width: T, corner_radius: T) -> String {
+        svg::document(self, stroke_width, corner_radius)
+    }
+
+    /// Returns an iterator over the lines of the coarsest levels of the HTree which together have at
+    /// most `max_segments` lines.
+    ///
+    /// Levels are included from the coarsest on as long as the next one still fits into the budget,
+    /// and are yielded in the emission order of the HTree. A budget of 0 yields no lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(10);
+    /// // levels 0 to 5 have 63 lines, level 6 would add another 64
+    /// assert_eq!(htree.with_budget(100).count(),63);
+    /// ```
+    pub fn with_budget(&self, max_segments: usize) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let mut levels = 0;
+        while levels <= self.order && self.first_index(levels as u32 + 1) <= max_segments {
+            levels += 1;
+        }
+        let truncated = HTree {
+            order: levels.saturating_sub(1),
+            ..*self
+        };
+        truncated.into_iter().take(self.first_index(levels as u32))
+    }
+
+    /// Returns the coordinates of the lines of the HTree as `f32`, interleaved as `[x0, y0, x1, y1, ...]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(0);
+    /// assert_eq!(htree.flat_f32(),vec![0.25,0.35355338,0.75,0.35355338]);
+    /// ```
+    pub fn flat_f32(&self) -> Vec<f32> {
+        let value = |value: T| value.to_f32().unwrap();
+        self.into_iter()
+            .flat_map(|(start, stop)| [value(start.0), value(start.1), value(stop.0), value(stop.1)])
+            .collect()
+    }
+
+    /// Returns the coordinates of the lines of the HTree as half precision floats, interleaved like [`HTree::flat_f32`].
+    ///
+    /// Coordinates are converted to `f32` first and then rounded to the nearest `f16`. Half precision
+    /// has 11 significant bits, so coordinates between `0.5` and `1` are rounded to multiples of
+    /// `1/2048`. Lines up to about level 18 keep their exact positions, beyond that endpoints merge
+    /// and the finest lines degenerate to points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(0);
+    /// assert_eq!(htree.flat_f16()[0],half::f16::from_f32(0.25));
+    /// ```
+    #[cfg(feature = "half")]
+    pub fn flat_f16(&self) -> Vec<half::f16> {
+        self.flat_f32().into_iter().map(half::f16::from_f32).collect()
+    }
+
+    /// Returns the index of the line of `level` whose endpoints lie in the rectangle with index `rect_index`.
+    ///
+    /// Rectangles are those of [`HTree::debug_level_grid`], counted row by row on even levels and
+    /// column by column on odd levels, so every line joins [`HTree::branches`] consecutive rectangles.
+    /// The index counts the lines in the order they are yielded by [`HTree::into_iter`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `level` lies beyond the order of the HTree or `rect_index` beyond its grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(3);
+    /// // level 2 has 4 x 2 rectangles, rectangle 6 lies in the second row
+    /// assert_eq!(htree.index_of_cell(2,6),6);
+    /// ```
+    pub fn index_of_cell(&self, level: usize, rect_index: u32) -> usize {
+        assert!(level <= self.order, "level {level} is beyond the order {} of the HTree", self.order);
+        let (num_horizontal_rectangles, num_vertical_rectangles) = self.grid_dimensions(level as u32);
+        assert!(
+            rect_index < num_horizontal_rectangles * num_vertical_rectangles,
+            "rectangle {rect_index} is beyond the grid of level {level}"
+        );
+        self.index_of(level as u32, rect_index / self.branches)
+    }
+
+    /// Returns an iterator over the lines of the HTree in pixels of a `width` x `height` raster with
+    /// every endpoint moved to the center of the pixel it lies in.
+    ///
+    /// The bounding box is scaled uniformly and centered within the raster like in
+    /// [`HTree::rasterize_1bpp`]. Points sharing a coordinate are computed identically, so lines
+    /// meeting at a junction are still aligned after snapping and 1 pixel wide strokes show no seams.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(0);
+    /// assert_eq!(htree.pixel_snap(100,71).next(),Some(((25.5,35.5),(75.5,35.5))));
+    /// ```
+    pub fn pixel_snap(&self, width: u32, height: u32) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let (w, h) = (T::from(width).unwrap(), T::from(height).unwrap());
+        let (extent_width, extent_height) = self.extent();
+        let scale = (w / extent_width).min(h / extent_height);
+        let offset = ((w - scale * extent_width) * half(), (h - scale * extent_height) * half());
+        let snap = move |value: T, offset: T, size: T| {
+            let pixel = (offset + value * scale).floor().max(T::zero()).min(size - T::one());
+            pixel + half()
+        };
+        let h_tree = *self;
+        let pixel = move |point: (T, T)| {
+            let (x, y) = h_tree.relative(point);
+            (snap(x, offset.0, w), snap(y, offset.1, h))
+        };
+        self.into_iter().map(move |(start, stop)| (pixel(start), pixel(stop)))
+    }
+
+    /// Returns the affine maps of the iterated function system generating the HTree.
+    ///
+    /// Maps are given as `3 x 3` matrices acting on column vectors `(x, y, 1)`, so a point maps to
+    /// `(m[0][0] * x + m[0][1] * y + m[0][2], m[1][0] * x + m[1][1] * y + m[1][2])`. There is one map
+    /// per branch: map `i` swaps the axes and squeezes the bounding box onto its `i`-th of
+    /// [`HTree::branches`] vertical strips, which is where the `i`-th child of the first line and the
+    /// subtree below it lie. Together the maps carry the lines of every level onto the lines of the
+    /// next level, so the HTree is the first line together with all its images. For [`Shape::Classic`]
+    /// the maps are similarities shrinking by `1/sqrt(branches)`, otherwise they are merely affine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(3);
+    /// let maps=htree.ifs_maps();
+    /// let ((x,y),_)=htree.into_iter().next().unwrap();
+    /// let m=maps[0];
+    /// let image=(m[0][0]*x+m[0][1]*y+m[0][2],m[1][0]*x+m[1][1]*y+m[1][2]);
+    /// // the start of the first line maps onto the start of its first child
+    /// let (child,_)=htree.segment_at(1).unwrap();
+    /// assert!((image.0-child.0).abs()<1e-12 && (image.1-child.1).abs()<1e-12);
+    /// ```
+    pub fn ifs_maps(&self) -> Vec<[[T; 3]; 3]> {
+        let (width, height) = self.extent();
+        let strip = width / T::from(self.branches).unwrap();
+        // the maps act on the bounding box at the origin, `place` moves them onto the HTree
+        let (sign, shift) = if self.flipped { (-T::one(), height) } else { (T::one(), T::zero()) };
+        let place = [
+            [T::one(), T::zero(), self.origin.0],
+            [T::zero(), sign, self.origin.1 + shift],
+            [T::zero(), T::zero(), T::one()],
+        ];
+        let unplace = [
+            [T::one(), T::zero(), -self.origin.0],
+            [T::zero(), sign, -sign * (self.origin.1 + shift)],
+            [T::zero(), T::zero(), T::one()],
+        ];
+        let multiply = |a: [[T; 3]; 3], b: [[T; 3]; 3]| {
+            let mut product = [[T::zero(); 3]; 3];
+            for (row, product_row) in product.iter_mut().enumerate() {
+                for (column, value) in product_row.iter_mut().enumerate() {
+                    *value = (0..3).fold(T::zero(), |sum, k| sum + a[row][k] * b[k][column]);
+                }
+            }
+            product
+        };
+        (0..self.branches)
+            .map(|branch| {
+                let map = [
+                    [T::zero(), strip / height, strip * T::from(branch).unwrap()],
+                    [height / width, T::zero(), T::zero()],
+                    [T::zero(), T::zero(), T::one()],
+                ];
+                multiply(multiply(place, map), unplace)
+            })
+            .collect()
+    }
+
+    /// Returns the Graphviz DOT description of an undirected graph with a node per distinct endpoint
+    /// and an edge per line of the HTree.
+    ///
+    /// Nodes are numbered from 0 and labeled with their coordinates, endpoints closer than a small
+    /// fraction of the shortest line share a node. Lines ending on the middle of other lines do not
+    /// split them, so junctions do not show up as shared nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(0);
+    /// let dot=htree.to_dot();
+    /// assert!(dot.starts_with("graph {\n  0 [label=\"(0.25, 0.3535533905932738)\"];\n"));
+    /// assert!(dot.ends_with("  0 -- 1;\n}\n"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let graph = graph::Graph::new(self);
+        let mut ends = vec![(usize::MAX, 0); self.len()];
+        for (&(a, b), &line) in graph.edges.iter().zip(&graph.lines) {
+            let end = &mut ends[line];
+            if end.0 == usize::MAX {
+                end.0 = a;
+            }
+            end.1 = b;
+        }
+        let mut dot = String::from("graph {\n");
+        for (node, (x, y)) in graph.vertices.iter().enumerate() {
+            dot.push_str(&format!("  {node} [label=\"({}, {})\"];\n", x.to_f64().unwrap(), y.to_f64().unwrap()));
+        }
+        for (start, stop) in ends {
+            dot.push_str(&format!("  {start} -- {stop};\n"));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Returns the fraction of the bounding box covered by the lines of the HTree stroked `line_width` wide.
+    ///
+    /// Strokes have square caps like in [`HTree::outline`] and are clipped to the bounding box. The
+    /// area of their union is computed exactly on the grid of all their edges, so overlaps at the
+    /// junctions are only counted once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `line_width` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(0);
+    /// // a stroke of 0.6 x 0.1 including its caps in a box of 1 x 1/sqrt(2)
+    /// assert!((htree.fill_ratio(0.1)-0.06*2f64.sqrt()).abs()<1e-12);
+    /// ```
+    pub fn fill_ratio(&self, line_width: T) -> T {
+        assert!(line_width > T::zero(), "line_width must be positive");
+        let (width, height) = self.extent();
+        let (corner, opposite) = (self.origin, (self.origin.0 + width, self.origin.1 + height));
+        let rectangles: Vec<Line<T>> = self
+            .stroke_rectangles(line_width)
+            .into_iter()
+            .map(|(min, max)| {
+                (
+                    (min.0.max(corner.0), min.1.max(corner.1)),
+                    (max.0.min(opposite.0), max.1.min(opposite.1)),
+                )
+            })
+            .collect();
+        let epsilon = graph::epsilon(self).min(line_width / T::from(1000).unwrap());
+        outline::union_area(&rectangles, epsilon) / (width * height)
+    }
+
+    /// Returns an iterator over the lines of the HTree seen through a camera looking at `offset` with
+    /// magnification `zoom`.
+    ///
+    /// Every point `p` maps to `(p - offset) * zoom`, so `offset` ends up at the origin of the screen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(0);
+    /// let (start,stop)=htree.with_camera((0.25,0.0),2.0).next().unwrap();
+    /// assert_eq!((start.0,stop.0),(0.0,1.0));
+    /// ```
+    pub fn with_camera(&self, offset: (T, T), zoom: T) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let screen = move |(x, y): (T, T)| ((x - offset.0) * zoom, (y - offset.1) * zoom);
+        self.into_iter().map(move |(start, stop)| (screen(start), screen(stop)))
+    }
+
+    /// Returns an iterator over the lines of the deepest level of the HTree, like
+    /// `self.level(self.order())`.
+    ///
+    /// Lines are computed straight from their position within the level, so the coarser levels
+    /// are never visited, not even with the depth first emission orders.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(10);
+    /// assert_eq!(htree.deepest_level().count(),1024);
+    /// ```
+    pub fn deepest_level(&self) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let h_tree = *self;
+        let order_index = self.order as u32;
+        (0..self.branches.pow(order_index)).map(move |position| {
+            let iteration_index = if h_tree.is_depth_first() {
+                // the digits of the position are the ranks of the ancestors among their siblings
+                (1..=order_index).fold(0, |iteration_index, level| {
+                    let rank = position / h_tree.branches.pow(order_index - level) % h_tree.branches;
+                    h_tree.child_iteration_index(level - 1, iteration_index, rank)
+                })
+            } else {
+                h_tree.iteration_index(order_index, position)
+            };
+            h_tree.segment(order_index, iteration_index)
+        })
+    }
+
+    /// Returns an iterator over the lines of the HTree followed by the same lines rotated by 90 degrees
+    /// about the center of the bounding box.
+    ///
+    /// The HTree is symmetric under rotation by 180 degrees, so the figure as a whole maps onto itself
+    /// when rotated by 90 degrees. The rotated copy reaches beyond the bounding box unless it is square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(0).with_shape(htree::Shape::Square);
+    /// let lines:Vec<_>=htree.crossed().collect();
+    /// assert_eq!(lines,vec![((0.25,0.5),(0.75,0.5)),((0.5,0.25),(0.5,0.75))]);
+    /// ```
+    pub fn crossed(&self) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let (width, height) = self.extent();
+        let center = self.place((width * half(), height * half()));
+        let rotate = move |(x, y): (T, T)| (center.0 - (y - center.1), center.1 + (x - center.0));
+        self.into_iter().chain(self.into_iter().map(move |(start, stop)| (rotate(start), rotate(stop))))
+    }
+
+    /// Returns the line of the HTree closest to `p`.
+    ///
+    /// Lines are looked up with [`HTree::segments_in_rect`] in a square around `p` which grows until
+    /// it holds a line closer than half its side, so only the lines near `p` are visited. Of lines at
+    /// the same distance the one yielded first by that search is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(6);
+    /// assert_eq!(htree.nearest_segment((0.5,0.36)),htree.segment_at(0).unwrap());
+    /// ```
+    pub fn nearest_segment(&self, p: (T, T)) -> ((T, T), (T, T)) {
+        let (width, height) = self.extent();
+        // beyond this size the square holds every line
+        let offset = self.relative(p);
+        let covering = (offset.0.abs() + width).max(offset.1.abs() + height);
+        let mut radius = self.segment_length(self.order as u32);
+        loop {
+            let nearest = self
+                .segments_in_rect((p.0 - radius, p.1 - radius), (p.0 + radius, p.1 + radius))
+                .map(|line| (distance_to_segment(p, line), line))
+                .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            match nearest {
+                Some((distance, line)) if distance <= radius || radius >= covering => return line,
+                _ => radius = radius + radius,
+            }
+        }
+    }
+
+    /// Returns the distance from `p` to the closest line of the HTree.
+    ///
+    /// Every call searches the lines around `p` with [`HTree::nearest_segment`]. Sampling a distance
+    /// field at many points is faster with a [`SpatialIndex`] built once, see [`HTree::build_spatial_index`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(0);
+    /// assert!((htree.distance_to((0.5,0.5))-(0.5-0.5f64.sqrt()/2.0)).abs()<1e-12);
+    /// ```
+    pub fn distance_to(&self, p: (T, T)) -> T {
+        distance_to_segment(p, self.nearest_segment(p))
+    }
+
+    /// Returns an iterator over the lines of the HTree split wherever another line ends on them.
+    ///
+    /// The pieces are the edges of a planar graph: they only meet at their endpoints, and endpoints
+    /// closer than a small fraction of the shortest line are merged into one point. Pieces are
+    /// yielded line by line from start to stop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(1);
+    /// // both children split in their middle
+    /// assert_eq!(htree.planarized().count(),5);
+    /// ```
+    pub fn planarized(&self) -> impl Iterator<Item = ((T, T), (T, T))> {
+        let graph = graph::Graph::new(self);
+        let vertices = graph.vertices;
+        graph.edges.into_iter().map(move |(a, b)| (vertices[a], vertices[b]))
+    }
+
+    /// Returns the `(width, height)` in pixels of a canvas fitting the bounding box of the HTree
+    /// whose longer side is `longest_edge` pixels long.
+    ///
+    /// The shorter side is rounded to the nearest pixel, but is at least 1 pixel long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{HTree, Shape};
+    /// let htree:HTree<f32>=HTree::new(10);
+    /// assert_eq!(htree.canvas_size(1000),(1000,707));
+    /// assert_eq!(htree.with_shape(Shape::Square).canvas_size(1000),(1000,1000));
+    /// ```
+    pub fn canvas_size(&self, longest_edge: u32) -> (u32, u32) {
+        let (width, height) = self.extent();
+        let shorter = |ratio: T| (T::from(longest_edge).unwrap() * ratio).round().to_u32().unwrap_or(0).max(1);
+        if width >= height {
+            (longest_edge, shorter(height / width).min(longest_edge))
+        } else {
+            (shorter(width / height).min(longest_edge), longest_edge)
+        }
+    }
+
+    /// Returns an iterator over the lines of the HTree with their unit normal.
+    ///
+    /// The normal is the direction of the line rotated by a quarter turn, `(-dy, dx)` normalized,
+    /// which is the side [`HTree::ribbons`] offsets the first two corners of a quad to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(1);
+    /// let normals:Vec<(f32,f32)>=htree.iter_with_normal().map(|(_,normal)|normal).collect();
+    /// assert_eq!(normals,vec![(0.0,1.0),(-1.0,0.0),(-1.0,0.0)]);
+    /// ```
+    pub fn iter_with_normal(&self) -> impl Iterator<Item = (Line<T>, (T, T))> {
+        self.into_iter().map(|(start, stop)| {
+            let (dx, dy) = (stop.0 - start.0, stop.1 - start.1);
+            let length = dx.hypot(dy);
+            ((start, stop), (-dy / length, dx / length))
+        })
+    }
+
+    /// Returns the highest order whose shortest lines are still at least one pixel long when the
+    /// HTree returned by [`HTree::new`] is scaled as large as possible into an image of `width x height`
+    /// pixels without distorting it.
+    ///
+    /// Lines of higher orders would be shorter than a pixel and add nothing visible. The order is 0
+    /// if even the first line is shorter than a pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// assert_eq!(HTree::<f64>::useful_order_for_resolution(1000,1000),17);
+    /// ```
+    pub fn useful_order_for_resolution(width: u32, height: u32) -> usize {
+        let (extent_width, extent_height) = HTree::<T>::new(0).extent();
+        let scale = (T::from(width).unwrap() / extent_width).min(T::from(height).unwrap() / extent_height);
+        HTree::until_length(scale.recip(), extent_height).order
+    }
+
+    /// Returns the lines of the HTree split into `(horizontals, verticals)` by their orientation.
+    ///
+    /// Both keep the order yielded by [`HTree::iter_with_orientation`], so a plotter with one pen per
+    /// direction can draw all horizontal lines and then all vertical lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(2);
+    /// let (horizontals,verticals)=htree.partition_by_orientation();
+    /// assert_eq!((horizontals.len(),verticals.len()),(5,2));
+    /// ```
+    pub fn partition_by_orientation(&self) -> (Vec<Line<T>>, Vec<Line<T>>) {
+        let (mut horizontals, mut verticals) = (Vec::new(), Vec::new());
+        for (orientation, line) in self.iter_with_orientation() {
+            match orientation {
+                Orientation::Horizontal => horizontals.push(line),
+                Orientation::Vertical => verticals.push(line),
+            }
+        }
+        (horizontals, verticals)
+    }
+
+    /// Returns the lines of the HTree sorted by the index of their midpoint along a Hilbert curve.
+    ///
+    /// The bounding box is divided into a grid of `resolution x resolution` cells, rounded up to a
+    /// power of two, which the Hilbert curve visits one after another so that consecutive lines lie
+    /// close to each other. Lines sharing a cell keep the order yielded by [`HTree::into_iter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(1);
+    /// // the curve runs through the lower left cell before the lower right one
+    /// let lines:Vec<_>=[1,0,2].into_iter().map(|index|htree.segment_at(index).unwrap()).collect();
+    /// assert_eq!(htree.hilbert_ordered(2),lines);
+    /// ```
+    pub fn hilbert_ordered(&self, resolution: u32) -> Vec<Line<T>> {
+        let side = resolution.max(1).next_power_of_two();
+        let (width, height) = self.extent();
+        let cell = |value: T, length: T| {
+            (value / length * T::from(side).unwrap()).floor().to_u32().unwrap_or(0).min(side - 1)
+        };
+        let mut lines: Vec<(u64, Line<T>)> = self
+            .into_iter()
+            .zip(self.midpoints())
+            .map(|(line, midpoint)| {
+                let (x, y) = self.relative(midpoint);
+                (hilbert_index(side, cell(x, width), cell(y, height)), line)
+            })
+            .collect();
+        lines.sort_by_key(|&(index, _)| index);
+        lines.into_iter().map(|(_, line)| line).collect()
+    }
+
+    /// Returns the lines of the HTree in a canonical form independent of their order and direction.
+    ///
+    /// Coordinates are multiplied by `10^decimals` and rounded to integers, every line starts at the
+    /// smaller of its two endpoints and the lines are sorted. HTrees made of the same lines therefore
+    /// yield the same canonical form regardless of their [`EmissionOrder`] or floating point type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{EmissionOrder, HTree};
+    /// let htree:HTree<f64>=HTree::new(6);
+    /// let depth_first=htree.with_emission_order(EmissionOrder::DepthFirst);
+    /// assert_eq!(htree.canonical_segments(6),depth_first.canonical_segments(6));
+    /// assert_eq!(htree.canonical_segments(4),HTree::<f32>::new(6).canonical_segments(4));
+    /// ```
+    pub fn canonical_segments(&self, decimals: u32) -> Vec<((i64, i64), (i64, i64))> {
+        let factor = T::from(10).unwrap().powi(decimals as i32);
+        let quantize = |value: T| (value * factor).round().to_i64().unwrap();
+        let mut lines: Vec<_> = self
+            .into_iter()
+            .map(|(start, stop)| {
+                let (start, stop) = ((quantize(start.0), quantize(start.1)), (quantize(stop.0), quantize(stop.1)));
+                (start.min(stop), start.max(stop))
+            })
+            .collect();
+        lines.sort_unstable();
+        lines
+    }
+
+    /// Returns an iterator over the lines of the HTree clipped to the disc around `center` of radius `radius`.
+    ///
+    /// Lines fully outside the disc are dropped and lines crossing its boundary are cut off where
+    /// they cross it. Lines which only touch the boundary are dropped as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(0);
+    /// let clipped:Vec<_>=htree.clipped_to_circle((0.5,0.0),0.3).collect();
+    /// assert_eq!(clipped,vec![]);
+    /// let clipped:Vec<_>=htree.clipped_to_circle((0.5,0.5f64.sqrt()/2.0),0.125).collect();
+    /// assert_eq!(clipped,vec![((0.375,0.5f64.sqrt()/2.0),(0.625,0.5f64.sqrt()/2.0))]);
+    /// ```
+    pub fn clipped_to_circle(&self, center: (T, T), radius: T) -> impl Iterator<Item = ((T, T), (T, T))> {
+        self.into_iter().filter_map(move |(start, stop)| {
+            // solve `|start + t * direction - center| = radius` for the parameters `t` of the crossings
+            let direction = (stop.0 - start.0, stop.1 - start.1);
+            let offset = (start.0 - center.0, start.1 - center.1);
+            let a = direction.0 * direction.0 + direction.1 * direction.1;
+            let b = offset.0 * direction.0 + offset.1 * direction.1;
+            let c = offset.0 * offset.0 + offset.1 * offset.1 - radius * radius;
+            let discriminant = b * b - a * c;
+            if discriminant <= T::zero() {
+                return None;
+            }
+            let root = discriminant.sqrt();
+            let first = ((-b - root) / a).max(T::zero());
+            let last = ((-b + root) / a).min(T::one());
+            if first >= last {
+                return None;
+            }
+            let point = |t: T| (start.0 + direction.0 * t, start.1 + direction.1 * t);
+            Some((point(first), point(last)))
+        })
+    }
+
+    /// Returns an iterator over the lines of the HTree as parametric functions of their start and direction.
+    ///
+    /// Lines are yielded in the order of [`HTree::into_iter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::with_size(0,4.0,2.0);
+    /// let line=htree.parametric_segments().next().unwrap();
+    /// assert_eq!((line.origin,line.dir),((1.0,1.0),(2.0,0.0)));
+    /// assert_eq!(line.eval(0.5),(2.0,1.0));
+    /// ```
+    pub fn parametric_segments(&self) -> impl Iterator<Item = ParamLine<T>> {
+        self.into_iter().map(|(start, stop)| ParamLine {
+            origin: start,
+            dir: (stop.0 - start.0, stop.1 - start.1),
+        })
+    }
+
+    /// Returns the lines of a morph between this HTree at `t = 0` and `other` at `t = 1`.
+    ///
+    /// Lines are paired by their id, see [`HTree::iter_with_id`], and lines present in both HTrees
+    /// move linearly from one to the other. Lines of the levels only the HTree of higher order has
+    /// grow out of their midpoint, where they branch off their parent, to their full length, or
+    /// shrink into it when morphing towards the HTree of lower order. Such lines are left out
+    /// while they are collapsed into a single point, so `t = 0` yields exactly the lines of this
+    /// HTree and `t = 1` those of `other`. Lines are returned in the order yielded by the HTree of
+    /// higher order, the lines of `other` if both orders are equal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both HTrees have a different number of branches, their lines could not be paired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let coarse:HTree<f32>=HTree::new(1);
+    /// let fine:HTree<f32>=HTree::new(3);
+    /// assert_eq!(coarse.lerp(&fine,0.0),coarse.into_iter().collect::<Vec<_>>());
+    /// assert_eq!(coarse.lerp(&fine,0.5).len(),fine.len());
+    /// ```
+    pub fn lerp(&self, other: &HTree<T>, t: T) -> Vec<Line<T>> {
+        assert_eq!(
+            self.branches, other.branches,
+            "HTrees with {} and {} branches cannot be morphed",
+            self.branches, other.branches
+        );
+        let mix = |a: (T, T), b: (T, T)| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+        let towards_other = other.order >= self.order;
+        let (higher, lower, grown) = if towards_other {
+            (*other, *self, t)
+        } else {
+            (*self, *other, T::one() - t)
+        };
+        let lower = lower.with_emission_order(EmissionOrder::AsBuilt);
+        let half: T = half();
+        higher
+            .iter_with_id()
+            .filter_map(|(id, line)| match lower.segment_at(id as usize) {
+                Some(counterpart) => {
+                    let (from, to) = if towards_other { (counterpart, line) } else { (line, counterpart) };
+                    Some((mix(from.0, to.0), mix(from.1, to.1)))
+                }
+                None if grown <= T::zero() => None,
+                None => {
+                    let (start, stop) = line;
+                    let middle = ((start.0 + stop.0) * half, (start.1 + stop.1) * half);
+                    let scale =
+                        |point: (T, T)| (middle.0 + (point.0 - middle.0) * grown, middle.1 + (point.1 - middle.1) * grown);
+                    Some((scale(start), scale(stop)))
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the area covered by the lines of the HTree stroked `width` wide with butt caps.
+    ///
+    /// Every stroke is the rectangle of its line grown by `width/2` to both sides, ending flush with
+    /// the endpoints. The area of their union is computed exactly on the grid of all their edges,
+    /// like in [`HTree::fill_ratio`], so the ends of lines which overlap the strokes of their
+    /// children at the junctions are only counted once. Unlike [`HTree::fill_ratio`] the strokes are
+    /// not clipped to the bounding box.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(1);
+    /// // both children overlap half a square of the stroke of the first line
+    /// let area=htree.total_length()*0.1-2.0*0.1*0.05;
+    /// assert!((htree.stroked_area(0.1)-area).abs()<1e-12);
+    /// ```
+    pub fn stroked_area(&self, width: T) -> T {
+        assert!(width > T::zero(), "width must be positive");
+        let half_width = width * half();
+        let rectangles: Vec<Line<T>> = self
+            .into_iter()
+            .map(|(start, stop)| {
+                let (min, max) = ((start.0.min(stop.0), start.1.min(stop.1)), (start.0.max(stop.0), start.1.max(stop.1)));
+                if start.1 == stop.1 {
+                    ((min.0, min.1 - half_width), (max.0, max.1 + half_width))
+                } else {
+                    ((min.0 - half_width, min.1), (max.0 + half_width, max.1))
+                }
+            })
+            .collect();
+        let epsilon = graph::epsilon(self).min(width / T::from(1000).unwrap());
+        outline::union_area(&rectangles, epsilon)
+    }
+
+    /// Returns all lines of the HTree in the order yielded by [`HTree::into_iter`].
+    ///
+    /// Instead of locating every line by its index, the lines of each level are generated by two
+    /// nested loops over its grid from the centers of the rectangles of that level, computed once
+    /// per level. The result is identical to collecting the iterator, see `benches/collect.rs` for
+    /// the speedup. Depth first orders do not yield levels in one piece and HTrees with length
+    /// variation are off the grid, both are collected from the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(6);
+    /// assert_eq!(htree.collect_all(),htree.into_iter().collect::<Vec<_>>());
+    /// ```
+    pub fn collect_all(&self) -> Vec<Line<T>> {
+        if self.is_depth_first() || self.length_variation.is_some() {
+            return self.into_iter().collect();
+        }
+        let (width, height) = self.extent();
+        let branches = self.branches as usize;
+        let mut lines = Vec::with_capacity(self.len());
+        for order_index in 0..=self.order as u32 {
+            let (num_horizontal_rectangles, num_vertical_rectangles) = self.grid_dimensions(order_index);
+            // same arithmetic as `HTree::segment` so the coordinates are bitwise equal
+            let centers = |count: u32, length: T| -> Vec<T> {
+                (0..count).map(|i| (T::from(i).unwrap() + half()) / T::from(count).unwrap() * length).collect()
+            };
+            let xs: Vec<T> = centers(num_horizontal_rectangles, width)
+                .into_iter()
+                .map(|x| self.place((x, T::zero())).0)
+                .collect();
+            let ys: Vec<T> = centers(num_vertical_rectangles, height)
+                .into_iter()
+                .map(|y| self.place((T::zero(), y)).1)
+                .collect();
+            let vertical = order_index % 2 == 1;
+            let (rows, columns) = self.line_grid(order_index);
+            let row_major = match self.emission_order {
+                EmissionOrder::RowMajor => true,
+                EmissionOrder::ColMajor => false,
+                _ => !vertical,
+            };
+            let (outer, inner) = if row_major { (rows, columns) } else { (columns, rows) };
+            for a in 0..outer as usize {
+                for b in 0..inner as usize {
+                    let (row, column) = if row_major { (a, b) } else { (b, a) };
+                    lines.push(if vertical {
+                        ((xs[column], ys[branches * row]), (xs[column], ys[branches * row + branches - 1]))
+                    } else {
+                        ((xs[branches * column], ys[row]), (xs[branches * column + branches - 1], ys[row]))
+                    });
+                }
+            }
+        }
+        lines
+    }
+
+    /// Returns `n` contiguous ranges of line indices which together cover `0..len()`.
+    ///
+    /// The ranges differ in length by at most one, longer ranges first, so they spread the lines
+    /// evenly over threads feeding them to [`HTree::index_range_iter`]. Returns no ranges if `n` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(3);
+    /// assert_eq!(htree.balanced_ranges(4),vec![0..4,4..8,8..12,12..15]);
+    /// ```
+    pub fn balanced_ranges(&self, n: usize) -> Vec<std::ops::Range<usize>> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let (base, longer) = (self.len() / n, self.len() % n);
+        let mut start = 0;
+        (0..n)
+            .map(|range| {
+                let end = start + base + usize::from(range < longer);
+                let range = start..end;
+                start = end;
+                range
+            })
+            .collect()
+    }
 
-#[derive(Clone, Copy, Debug)]
-pub struct HTree<T> {
-    order: usize,
-    _marker: PhantomData<T>,
-}
+    /// Returns a triangle mesh of the lines of the HTree stroked `width` wide as `(vertices, indices)`.
+    ///
+    /// Every line becomes the quad of [`HTree::ribbons`], its 4 vertices followed by the indices of
+    /// the two triangles `[0, 1, 2]` and `[0, 2, 3]` splitting it. Lines only meet at T junctions
+    /// where a line ends in the middle of the stroke of its child, which covers the butt end, so no
+    /// join geometry is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(4);
+    /// let (vertices,indices)=htree.to_line_mesh(0.01);
+    /// assert_eq!((vertices.len(),indices.len()),(4*31,6*31));
+    /// ```
+    pub fn to_line_mesh(&self, width: T) -> (Vec<(T, T)>, Vec<u32>) {
+        let mut vertices = Vec::with_capacity(4 * self.len());
+        let mut indices = Vec::with_capacity(6 * self.len());
+        for quad in self.ribbons(width) {
+            let first = vertices.len() as u32;
+            indices.extend([0, 1, 2, 0, 2, 3].map(|corner| first + corner));
+            vertices.extend(quad);
+        }
+        (vertices, indices)
+    }
 
-pub struct HTreeIterator<T>
-where
-    T: Float,
-{
-    h_tree: HTree<T>,
-    index: usize,
-}
+    /// Returns an iterator over the lines of the HTree with the total length of all lines up to
+    /// and including them.
+    ///
+    /// Lengths accumulate in the order yielded by [`HTree::into_iter`], so the last one is the
+    /// [`HTree::total_length`] up to rounding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{HTree, Shape};
+    /// let htree:HTree<f64>=HTree::new(1).with_shape(Shape::Square);
+    /// let lengths:Vec<f64>=htree.iter_with_arclength().map(|(length,_)|length).collect();
+    /// assert_eq!(lengths,vec![0.5,1.0,1.5]);
+    /// ```
+    pub fn iter_with_arclength(&self) -> impl Iterator<Item = (T, Line<T>)> {
+        self.into_iter().scan(T::zero(), |total, (start, stop)| {
+            *total = *total + (stop.0 - start.0).hypot(stop.1 - start.1);
+            Some((*total, (start, stop)))
+        })
+    }
 
-impl<T> HTree<T>
-where
-    T: Float,
-{
+    /// Returns a [`SpatialIndex`] of the lines of the HTree bucketed into a grid of `cells x cells`
+    /// over its bounding box.
+    ///
+    /// Building the index visits every line once, afterwards [`SpatialIndex::nearest`] only checks
+    /// the lines in the cells around the query point. Finer grids pay off for deeper HTrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(6);
+    /// let index=htree.build_spatial_index(16);
+    /// assert_eq!(index.nearest((0.5,0.36)).0,0);
+    /// ```
+    pub fn build_spatial_index(&self, cells: u32) -> SpatialIndex<T> {
+        SpatialIndex::new(self, cells)
+    }
 
-    /// Returns an instance of HTree up to specified order.
-    /// 
+    /// Returns the closed rings bounding the lines of the HTree stroked `width` wide, for filling
+    /// with a fill rule.
+    ///
+    /// Strokes have square caps and their union is traced exactly like in [`HTree::outline`], but
+    /// holes enclosed by the strokes and parts not touching the rest are kept, each bounded by its
+    /// own ring. Outer rings run counter clockwise and rings around holes clockwise with the y axis
+    /// pointing up, the other way round in image coordinates, so both the even-odd and the nonzero
+    /// rule fill exactly the strokes. Every ring repeats its first vertex at its end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is not positive.
+    ///
     /// # Examples
-    /// 
+    ///
     /// ```
     /// use htree::HTree;
-    /// let htree:HTree<f32>=HTree::new(10);
+    /// let htree:HTree<f64>=HTree::new(1);
+    /// let rings=htree.fill_rings(0.05);
+    /// assert_eq!(rings.len(),1);
+    /// assert_eq!(rings[0].len(),12+1);
     /// ```
-    pub fn new(order: usize) -> HTree<T> {
-        HTree {
-            order,
-            _marker: PhantomData {},
+    pub fn fill_rings(&self, width: T) -> Vec<Vec<(T, T)>> {
+        assert!(width > T::zero(), "width must be positive");
+        let epsilon = graph::epsilon(self).min(width / T::from(1000).unwrap());
+        let mut rings = outline::rings(&self.stroke_rectangles(width), epsilon);
+        for ring in &mut rings {
+            ring.push(ring[0]);
+        }
+        rings
+    }
+
+    /// Returns which symmetries of the bounding box map the lines of the HTree onto themselves.
+    ///
+    /// Every transform is applied to all lines, which are then compared to the original lines
+    /// regardless of their order and direction with coordinates rounded to a fraction of the
+    /// shortest line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::{HTree, Symmetries};
+    /// let htree:HTree<f64>=HTree::new(5);
+    /// let symmetries=Symmetries{vertical_mirror:true,horizontal_mirror:true,half_turn:true,..Symmetries::default()};
+    /// assert_eq!(htree.symmetries(),symmetries);
+    /// ```
+    pub fn symmetries(&self) -> Symmetries {
+        let (width, height) = self.extent();
+        let epsilon = graph::epsilon(self);
+        let round = |value: T| (value / epsilon).round().to_i64().unwrap();
+        let quantize = |(x, y): (T, T)| (round(x), round(y));
+        let canonical = |map: &dyn Fn((T, T)) -> (T, T)| {
+            let mut lines: Vec<_> = self
+                .into_iter()
+                .map(|(start, stop)| {
+                    let (start, stop) = (quantize(map(self.unplace(start))), quantize(map(self.unplace(stop))));
+                    (start.min(stop), start.max(stop))
+                })
+                .collect();
+            lines.sort_unstable();
+            lines
+        };
+        let original = canonical(&|point| point);
+        let invariant = |map: &dyn Fn((T, T)) -> (T, T)| canonical(map) == original;
+        let square = (width - height).abs() <= epsilon;
+        Symmetries {
+            vertical_mirror: invariant(&|(x, y)| (width - x, y)),
+            horizontal_mirror: invariant(&|(x, y)| (x, height - y)),
+            half_turn: invariant(&|(x, y)| (width - x, height - y)),
+            quarter_turn: square && invariant(&|(x, y)| (height - y, x)),
+            diagonal_mirror: square && invariant(&|(x, y)| (y, x)),
+            antidiagonal_mirror: square && invariant(&|(x, y)| (height - y, width - x)),
         }
     }
 }
-impl<T> Iterator for HTreeIterator<T>
+
+impl<T> HTree<T>
 where
     T: Float,
 {
-    type Item = ((T, T), (T, T));
-    fn next(&mut self) -> Option<Self::Item> {
-        self.index += 1;
-        let order_index = self.index.ilog2() as u32;
-        if order_index > self.h_tree.order as u32 {
+    /// Returns the `(width, height)` of the bounding box.
+    fn extent(&self) -> (T, T) {
+        self.size.unwrap_or((T::one(), self.aspect()))
+    }
+
+    /// Emits the trace event of the line at `index`, counted from 1, for [`HTreeIterator`].
+    #[cfg(feature = "tracing")]
+    fn trace_line(&self, index: usize, order_index: u32, iteration_index: u32) {
+        let (num_horizontal_rectangles, num_vertical_rectangles) = self.grid_dimensions(order_index);
+        tracing::trace!(
+            index,
+            order_index,
+            iteration_index,
+            num_horizontal_rectangles,
+            num_vertical_rectangles,
+            "next line"
+        );
+    }
+
+    /// Moves a point of the bounding box at the origin into the bounding box of the HTree.
+    fn place(&self, (x, y): (T, T)) -> (T, T) {
+        let y = if self.flipped { self.extent().1 - y } else { y };
+        (self.origin.0 + x, self.origin.1 + y)
+    }
+
+    /// Moves a point of the bounding box of the HTree back into the bounding box at the origin.
+    fn unplace(&self, point: (T, T)) -> (T, T) {
+        let (x, y) = self.relative(point);
+        (x, if self.flipped { self.extent().1 - y } else { y })
+    }
+
+    /// Returns a point relative to the top left corner of the bounding box.
+    fn relative(&self, (x, y): (T, T)) -> (T, T) {
+        (x - self.origin.0, y - self.origin.1)
+    }
+
+    /// Calls `plot` with the row major index of every pixel of every line drawn into a `width` x `height` image.
+    ///
+    /// The bounding box is scaled uniformly and centered within the image.
+    fn rasterize(&self, width: u32, height: u32, mut plot: impl FnMut(usize)) {
+        let (w, h) = (T::from(width).unwrap(), T::from(height).unwrap());
+        let (extent_width, extent_height) = self.extent();
+        let scale = (w / extent_width).min(h / extent_height);
+        let offset = ((w - scale * extent_width) * half(), (h - scale * extent_height) * half());
+        let pixel = |point: (T, T)| {
+            let (x, y) = self.relative(point);
+            let column = (offset.0 + x * scale).floor().to_i64().unwrap_or(0).clamp(0, width as i64 - 1);
+            let row = (offset.1 + y * scale).floor().to_i64().unwrap_or(0).clamp(0, height as i64 - 1);
+            (column, row)
+        };
+        for (start, stop) in self.into_iter() {
+            bresenham(pixel(start), pixel(stop), |column, row| {
+                plot(row as usize * width as usize + column as usize);
+            });
+        }
+    }
+
+    /// Returns the `(min, max)` corners of every line grown by `width/2` on all sides.
+    fn stroke_rectangles(&self, width: T) -> Vec<Line<T>> {
+        let half_width = width * half();
+        self.into_iter()
+            .map(|(start, stop)| {
+                (
+                    (start.0.min(stop.0) - half_width, start.1.min(stop.1) - half_width),
+                    (start.0.max(stop.0) + half_width, start.1.max(stop.1) + half_width),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the height of the bounding box relative to its width given by the shape.
+    fn aspect(&self) -> T {
+        match self.shape {
+            Shape::Classic => self.classic_aspect(),
+            Shape::Square => T::one(),
+        }
+    }
+
+    /// Returns the height relative to the width for which every level shrinks the lines by the same factor.
+    fn classic_aspect(&self) -> T {
+        if self.branches == 2 {
+            // `sqrt(2)/2` rounds like `1/sqrt(2)` and avoids a second rounding
+            let two = T::one() + T::one();
+            two.sqrt() / two
+        } else {
+            T::from(self.branches).unwrap().sqrt().recip()
+        }
+    }
+
+    /// Returns the number of rectangles the unit box is split into horizontally and vertically at a level.
+    fn grid_dimensions(&self, level: u32) -> (u32, u32) {
+        let num_horizontal_rectangles = self.branches.pow(level / 2 + 1);
+        let num_vertical_rectangles = self.branches.pow(level.div_ceil(2));
+        debug_assert!(
+            num_horizontal_rectangles == num_vertical_rectangles * self.branches.pow(1 - level % 2),
+            "grid of level {level} is not square-ish"
+        );
+        (num_horizontal_rectangles, num_vertical_rectangles)
+    }
+
+    /// Returns the length of a single line at a level.
+    fn segment_length(&self, level: u32) -> T {
+        let (num_horizontal_rectangles, num_vertical_rectangles) = self.grid_dimensions(level);
+        let cells = T::from(self.branches - 1).unwrap();
+        let (width, height) = self.extent();
+        if level % 2 == 1 {
+            height * cells / T::from(num_vertical_rectangles).unwrap()
+        } else {
+            width * cells / T::from(num_horizontal_rectangles).unwrap()
+        }
+    }
+
+    /// Returns the level and the position within that level of the line with index `index`.
+    ///
+    /// Returns `None` if the index lies beyond the order of the HTree.
+    fn locate(&self, index: usize) -> Option<(u32, u32)> {
+        // indices beyond the last line of any supported order overflow, so there is no line
+        let (order_index, first) = if self.branches == 2 {
+            let order_index = index.checked_add(1)?.ilog2();
+            (order_index, (1usize << order_index) - 1)
+        } else {
+            let (mut order_index, mut first, mut count) = (0, 0usize, 1usize);
+            while index >= first.checked_add(count)? {
+                first += count;
+                count = count.checked_mul(self.branches as usize)?;
+                order_index += 1;
+            }
+            (order_index, first)
+        };
+        if order_index > self.order as u32 {
             return None;
         }
-        let iteration_index = self.index as u32 - (1u32 << order_index);
+        Some((order_index, (index - first) as u32))
+    }
+
+    /// Returns the index of the line which is yielded at `position` within level `order_index`.
+    fn iteration_index(&self, order_index: u32, position: u32) -> u32 {
+        // lines of a level form a grid of `rows` x `columns`
+        let (rows, columns) = self.line_grid(order_index);
+        let (row, column) = match self.emission_order {
+            // depth first orders do not yield levels in one piece, see `HTree::locate_line`
+            EmissionOrder::AsBuilt | EmissionOrder::DepthFirst | EmissionOrder::Optimized => return position,
+            EmissionOrder::RowMajor => (position / columns, position % columns),
+            EmissionOrder::ColMajor => (position % rows, position / rows),
+        };
+        if order_index % 2 == 1 {
+            column * rows + row
+        } else {
+            row * columns + column
+        }
+    }
+
+    /// Returns the line with index `iteration_index` within level `order_index`.
+    fn segment(&self, order_index: u32, iteration_index: u32) -> ((T, T), (T, T)) {
+        let line = self.grid_segment(order_index, iteration_index);
+        let (start, stop) = match self.length_variation {
+            Some((variation, seed)) => self.varied_segment(order_index, iteration_index, line, variation, seed),
+            None => line,
+        };
+        (self.place(start), self.place(stop))
+    }
 
-        let num_vertical_rectangles = 1u32 << (order_index + 1) / 2;
-        let num_horizontal_rectangles = 1u32 << order_index / 2 + 1;
+    /// Returns the line of the grid with index `iteration_index` within level `order_index`, before any variation.
+    fn grid_segment(&self, order_index: u32, iteration_index: u32) -> ((T, T), (T, T)) {
+        let (num_horizontal_rectangles, num_vertical_rectangles) = self.grid_dimensions(order_index);
         let num_rectangles = num_vertical_rectangles * num_horizontal_rectangles;
-        assert_eq!(num_rectangles >= iteration_index * 2, true);
+        debug_assert!(num_rectangles >= iteration_index * self.branches);
 
-        let rectangle_index = 2 * iteration_index;
+        // a line connects the first and last of `branches` consecutive rectangles
+        let rectangle_index = self.branches * iteration_index;
+        let last_index = rectangle_index + self.branches - 1;
         let num_x_start;
         let num_y_start;
         let num_x_end;
@@ -73,32 +3267,550 @@ where
             //iteration_index=y+height*x
             num_y_start = rectangle_index % num_vertical_rectangles;
             num_x_start = (rectangle_index - num_y_start) / num_vertical_rectangles;
-            num_y_end = (rectangle_index + 1) % num_vertical_rectangles;
-            num_x_end = ((rectangle_index + 1) - num_y_end) / num_vertical_rectangles;
+            num_y_end = last_index % num_vertical_rectangles;
+            num_x_end = (last_index - num_y_end) / num_vertical_rectangles;
         } else {
             // direction ==0 -> horizontal
             //iteration_index=x+width*y
             num_x_start = rectangle_index % num_horizontal_rectangles;
             num_y_start = (rectangle_index - num_x_start) / num_horizontal_rectangles;
-            num_x_end = (rectangle_index + 1) % num_horizontal_rectangles;
-            num_y_end = ((rectangle_index + 1) - num_x_end) / num_horizontal_rectangles;
+            num_x_end = last_index % num_horizontal_rectangles;
+            num_y_end = (last_index - num_x_end) / num_horizontal_rectangles;
         }
 
-        let x_start: T = (T::from(num_x_start).unwrap() + T::from(0.5).unwrap())
+        let x_start: T = (T::from(num_x_start).unwrap() + half())
             / T::from(num_horizontal_rectangles).unwrap();
-        let x_end: T = (T::from(num_x_end).unwrap() + T::from(0.5).unwrap())
+        let x_end: T = (T::from(num_x_end).unwrap() + half())
             / T::from(num_horizontal_rectangles).unwrap();
-        let y_start: T = (T::from(num_y_start).unwrap() + T::from(0.5).unwrap())
+        let y_start: T = (T::from(num_y_start).unwrap() + half())
             / T::from(num_vertical_rectangles).unwrap();
-        let y_end: T = (T::from(num_y_end).unwrap() + T::from(0.5).unwrap())
+        let y_end: T = (T::from(num_y_end).unwrap() + half())
             / T::from(num_vertical_rectangles).unwrap();
-        Some((
-            (x_start, y_start * T::from(SCALE_HEIGHT).unwrap()),
-            (x_end, y_end * T::from(SCALE_HEIGHT).unwrap()),
-        ))
+        let (width, height) = self.extent();
+        ((x_start * width, y_start * height), (x_end * width, y_end * height))
+    }
+
+    /// Scales `line`, the grid line with index `iteration_index` within level `order_index`, by its
+    /// random factor about its midpoint, moved along with the scaling of all its ancestors.
+    fn varied_segment(
+        &self,
+        order_index: u32,
+        iteration_index: u32,
+        line: Line<T>,
+        variation: T,
+        seed: u64,
+    ) -> Line<T> {
+        let factor = |order_index: u32, iteration_index: u32| {
+            let id = (self.first_index(order_index) + iteration_index as usize) as u64;
+            let random = T::from(unit_interval(split_mix(seed ^ split_mix(id)))).unwrap();
+            T::one() + variation * (random + random - T::one())
+        };
+        let half: T = half();
+        let middle = |(start, stop): Line<T>| ((start.0 + stop.0) * half, (start.1 + stop.1) * half);
+
+        // every ancestor moves the point its child branches off by the scaling of its offset from the center
+        let center = middle(line);
+        let mut shift = (T::zero(), T::zero());
+        let (mut child_center, mut order_index_up, mut iteration_index_up) = (center, order_index, iteration_index);
+        while order_index_up > 0 {
+            (iteration_index_up, _) = self.parent_iteration_index(order_index_up, iteration_index_up);
+            order_index_up -= 1;
+            let parent_center = middle(self.grid_segment(order_index_up, iteration_index_up));
+            let stretch = factor(order_index_up, iteration_index_up) - T::one();
+            shift.0 = shift.0 + (child_center.0 - parent_center.0) * stretch;
+            shift.1 = shift.1 + (child_center.1 - parent_center.1) * stretch;
+            child_center = parent_center;
+        }
+
+        let scale = factor(order_index, iteration_index);
+        let place = |point: (T, T)| {
+            (
+                center.0 + shift.0 + (point.0 - center.0) * scale,
+                center.1 + shift.1 + (point.1 - center.1) * scale,
+            )
+        };
+        (place(line.0), place(line.1))
+    }
+
+    /// Returns the index of the first line of a level.
+    fn first_index(&self, order_index: u32) -> usize {
+        let branches = self.branches as usize;
+        (branches.pow(order_index) - 1) / (branches - 1)
+    }
+
+    /// Returns the position at which the line with index `iteration_index` is yielded within level `order_index`.
+    ///
+    /// This is the inverse of [`HTree::iteration_index`].
+    fn position(&self, order_index: u32, iteration_index: u32) -> u32 {
+        let (rows, columns) = self.line_grid(order_index);
+        let (row, column) = if order_index % 2 == 1 {
+            (iteration_index % rows, iteration_index / rows)
+        } else {
+            (iteration_index / columns, iteration_index % columns)
+        };
+        match self.emission_order {
+            EmissionOrder::AsBuilt | EmissionOrder::DepthFirst | EmissionOrder::Optimized => iteration_index,
+            EmissionOrder::RowMajor => row * columns + column,
+            EmissionOrder::ColMajor => column * rows + row,
+        }
+    }
+
+    /// Returns the index of the line the line with index `iteration_index` within level `order_index` is centered on.
+    fn parent(&self, order_index: u32, iteration_index: u32) -> Option<usize> {
+        if order_index == 0 {
+            return None;
+        }
+        let (parent_index, _) = self.parent_iteration_index(order_index, iteration_index);
+        Some(self.index_of(order_index - 1, parent_index))
+    }
+
+    /// Returns the index of the parent of a line within the previous level and the rank of the line among its siblings.
+    ///
+    /// Siblings are ranked along their parent line from its start to its stop.
+    fn parent_iteration_index(&self, order_index: u32, iteration_index: u32) -> (u32, u32) {
+        let (num_horizontal_rectangles, num_vertical_rectangles) = self.grid_dimensions(order_index);
+        let rectangle_index = self.branches * iteration_index;
+        // the line is centered on rectangle (column,row) of the previous level
+        if order_index % 2 == 1 {
+            let column = rectangle_index / num_vertical_rectangles;
+            let row = rectangle_index % num_vertical_rectangles / self.branches;
+            (
+                row * (num_horizontal_rectangles / self.branches) + column / self.branches,
+                column % self.branches,
+            )
+        } else {
+            let row = rectangle_index / num_horizontal_rectangles;
+            let column = rectangle_index % num_horizontal_rectangles / self.branches;
+            (
+                column * (num_vertical_rectangles / self.branches) + row / self.branches,
+                row % self.branches,
+            )
+        }
+    }
+
+    /// Returns the index within level `order_index + 1` of the child with rank `rank` of a line.
+    ///
+    /// This is the inverse of [`HTree::parent_iteration_index`].
+    fn child_iteration_index(&self, order_index: u32, iteration_index: u32, rank: u32) -> u32 {
+        let (num_horizontal_rectangles, num_vertical_rectangles) = self.grid_dimensions(order_index + 1);
+        if order_index.is_multiple_of(2) {
+            let per_row = num_horizontal_rectangles / self.branches;
+            let row = iteration_index / per_row;
+            let column = iteration_index % per_row * self.branches + rank;
+            column * (num_vertical_rectangles / self.branches) + row
+        } else {
+            let per_column = num_vertical_rectangles / self.branches;
+            let column = iteration_index / per_column;
+            let row = iteration_index % per_column * self.branches + rank;
+            row * (num_horizontal_rectangles / self.branches) + column
+        }
+    }
+
+    /// Returns whether the emission order keeps subtrees together instead of yielding level after level.
+    fn is_depth_first(&self) -> bool {
+        matches!(self.emission_order, EmissionOrder::DepthFirst | EmissionOrder::Optimized)
+    }
+
+    /// Returns the number of lines of a subtree whose root lies on level `order_index`.
+    fn subtree_len(&self, order_index: u32) -> usize {
+        self.first_index(self.order as u32 + 1 - order_index)
+    }
+
+    /// Returns the level and the index within that level of the line with index `index`.
+    ///
+    /// Returns `None` if the index lies beyond the order of the HTree.
+    fn locate_line(&self, index: usize) -> Option<(u32, u32)> {
+        if !self.is_depth_first() {
+            let (order_index, position) = self.locate(index)?;
+            return Some((order_index, self.iteration_index(order_index, position)));
+        }
+        if index >= self.len() {
+            return None;
+        }
+        // descend from the root, skipping the subtrees of the siblings before
+        let (mut order_index, mut iteration_index, mut remaining) = (0, 0, index);
+        while remaining > 0 {
+            remaining -= 1;
+            let subtree_len = self.subtree_len(order_index + 1);
+            let rank = (remaining / subtree_len) as u32;
+            remaining %= subtree_len;
+            iteration_index = self.child_iteration_index(order_index, iteration_index, rank);
+            order_index += 1;
+        }
+        Some((order_index, iteration_index))
+    }
+
+    /// Returns the index of the line with index `iteration_index` within level `order_index`.
+    ///
+    /// This is the inverse of [`HTree::locate_line`].
+    fn index_of(&self, order_index: u32, iteration_index: u32) -> usize {
+        if !self.is_depth_first() {
+            return self.first_index(order_index) + self.position(order_index, iteration_index) as usize;
+        }
+        if order_index == 0 {
+            return 0;
+        }
+        let (parent_index, rank) = self.parent_iteration_index(order_index, iteration_index);
+        self.index_of(order_index - 1, parent_index) + 1 + rank as usize * self.subtree_len(order_index)
+    }
+
+    /// Returns the node of the line with index `iteration_index` within level `order_index` and its subtree.
+    fn nested(&self, order_index: u32, iteration_index: u32) -> HNode<T> {
+        let children = if order_index < self.order as u32 {
+            (0..self.branches)
+                .map(|rank| self.nested(order_index + 1, self.child_iteration_index(order_index, iteration_index, rank)))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        HNode {
+            segment: self.segment(order_index, iteration_index),
+            children,
+        }
+    }
+
+    /// Returns the number of rows and columns the lines of a level are arranged in.
+    fn line_grid(&self, order_index: u32) -> (u32, u32) {
+        let (num_horizontal_rectangles, num_vertical_rectangles) = self.grid_dimensions(order_index);
+        if order_index % 2 == 1 {
+            (num_vertical_rectangles / self.branches, num_horizontal_rectangles)
+        } else {
+            (num_vertical_rectangles, num_horizontal_rectangles / self.branches)
+        }
+    }
+}
+
+/// Returns one half without converting from a floating point literal.
+///
+/// Iteration only converts integers into `T`, so types whose [`num::NumCast`] conversion rejects
+/// fractional values can still be iterated.
+fn half<T: Float>() -> T {
+    T::one() / (T::one() + T::one())
+}
+
+/// Returns the range of indices `i` whose centers `(i+0.5)/count` lie within `[low, high]`.
+///
+/// The range is widened by one on both sides to absorb rounding errors.
+fn index_range<T: Float>(low: T, high: T, count: u32) -> std::ops::Range<u32> {
+    let half: T = half();
+    let count_t = T::from(count).unwrap();
+    let first = (low * count_t - half).ceil() - T::one();
+    let last = (high * count_t - half).floor() + T::one();
+    let first = first.max(T::zero()).to_u32().unwrap_or(0);
+    let last = last.min(count_t - T::one()).to_u32().unwrap_or(0);
+    if low > high || first > last {
+        return 0..0;
+    }
+    first..last + 1
+}
+
+/// Returns whether an axis aligned line touches the rectangle spanned by `min` and `max`.
+fn segment_intersects_rect<T: Float>(segment: &((T, T), (T, T)), min: (T, T), max: (T, T)) -> bool {
+    let (start, stop) = *segment;
+    start.0.min(stop.0) <= max.0
+        && start.0.max(stop.0) >= min.0
+        && start.1.min(stop.1) <= max.1
+        && start.1.max(stop.1) >= min.1
+}
+
+/// Returns the smallest circle containing all points by Welzl's algorithm.
+///
+/// The points are shuffled in place, determined by `seed`, which makes the expected running time
+/// linear in their number.
+fn smallest_enclosing_circle<T: Float>(points: &mut [(T, T)], seed: u64) -> ((T, T), T) {
+    let mut state = seed;
+    for i in (1..points.len()).rev() {
+        state = split_mix(state);
+        points.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+    let two = T::from(2).unwrap();
+    let tolerance = T::epsilon() * T::from(64).unwrap();
+    let contains = |(center, radius): ((T, T), T), point: (T, T)| {
+        (point.0 - center.0).hypot(point.1 - center.1) <= radius + tolerance * radius.max(T::one())
+    };
+    let diameter = |a: (T, T), b: (T, T)| {
+        let center = ((a.0 + b.0) / two, (a.1 + b.1) / two);
+        (center, (a.0 - center.0).hypot(a.1 - center.1))
+    };
+    let circumcircle = |a: (T, T), b: (T, T), c: (T, T)| {
+        let (b, c) = ((b.0 - a.0, b.1 - a.1), (c.0 - a.0, c.1 - a.1));
+        let determinant = two * (b.0 * c.1 - b.1 * c.0);
+        let (b_squared, c_squared) = (b.0 * b.0 + b.1 * b.1, c.0 * c.0 + c.1 * c.1);
+        let offset = (
+            (c.1 * b_squared - b.1 * c_squared) / determinant,
+            (b.0 * c_squared - c.0 * b_squared) / determinant,
+        );
+        ((a.0 + offset.0, a.1 + offset.1), offset.0.hypot(offset.1))
+    };
+    let Some(&first) = points.first() else {
+        return ((T::zero(), T::zero()), T::zero());
+    };
+    let mut circle = (first, T::zero());
+    for i in 1..points.len() {
+        if contains(circle, points[i]) {
+            continue;
+        }
+        circle = (points[i], T::zero());
+        for j in 0..i {
+            if contains(circle, points[j]) {
+                continue;
+            }
+            circle = diameter(points[i], points[j]);
+            for k in 0..j {
+                if contains(circle, points[k]) {
+                    continue;
+                }
+                let (a, b, c) = (points[i], points[j], points[k]);
+                circle = circumcircle(a, b, c);
+                // collinear points are enclosed by the circle over the two farthest apart
+                if !circle.1.is_finite() {
+                    circle = [diameter(a, b), diameter(a, c), diameter(b, c)]
+                        .into_iter()
+                        .max_by(|x, y| x.1.partial_cmp(&y.1).unwrap())
+                        .unwrap();
+                }
+            }
+        }
+    }
+    circle
+}
+
+/// Returns the distance of `point` to the closest point of `line`.
+fn distance_to_segment<T: Float>(point: (T, T), line: Line<T>) -> T {
+    let (start, stop) = line;
+    let (dx, dy) = (stop.0 - start.0, stop.1 - start.1);
+    let length_squared = dx * dx + dy * dy;
+    let t = if length_squared > T::zero() {
+        (((point.0 - start.0) * dx + (point.1 - start.1) * dy) / length_squared).max(T::zero()).min(T::one())
+    } else {
+        T::zero()
+    };
+    (point.0 - start.0 - t * dx).hypot(point.1 - start.1 - t * dy)
+}
+
+/// Returns the point where `a` and `b` cross with both interiors, ignoring points within `epsilon` of an endpoint.
+///
+/// Collinear lines overlapping by more than `epsilon` cross at the start of the overlap.
+fn interior_crossing<T: Float>(a: Line<T>, b: Line<T>, epsilon: T) -> Option<(T, T)> {
+    let cross = |u: (T, T), v: (T, T)| u.0 * v.1 - u.1 * v.0;
+    let ((a_start, a_stop), (b_start, b_stop)) = (a, b);
+    let da = (a_stop.0 - a_start.0, a_stop.1 - a_start.1);
+    let db = (b_stop.0 - b_start.0, b_stop.1 - b_start.1);
+    let (length_a, length_b) = (da.0.hypot(da.1), db.0.hypot(db.1));
+    let offset = (b_start.0 - a_start.0, b_start.1 - a_start.1);
+    let point_at = |t: T| (a_start.0 + da.0 * t, a_start.1 + da.1 * t);
+    let denominator = cross(da, db);
+    if denominator.abs() <= epsilon * length_a.max(length_b) {
+        if cross(da, offset).abs() > epsilon * length_a {
+            return None;
+        }
+        // collinear, project `b` onto `a`
+        let along = |point: (T, T)| ((point.0 - a_start.0) * da.0 + (point.1 - a_start.1) * da.1) / length_a;
+        let low = along(b_start).min(along(b_stop)).max(T::zero());
+        let high = along(b_start).max(along(b_stop)).min(length_a);
+        if high - low <= epsilon {
+            return None;
+        }
+        return Some(point_at(low / length_a));
+    }
+    let t = cross(offset, db) / denominator;
+    let u = cross(offset, da) / denominator;
+    let interior = |parameter: T, length: T| parameter * length > epsilon && (T::one() - parameter) * length > epsilon;
+    if interior(t, length_a) && interior(u, length_b) {
+        Some(point_at(t))
+    } else {
+        None
+    }
+}
+
+/// Calls `plot` for every pixel on the line from `start` to `stop` with Bresenham's algorithm.
+fn bresenham(start: (i64, i64), stop: (i64, i64), mut plot: impl FnMut(i64, i64)) {
+    let (dx, dy) = ((stop.0 - start.0).abs(), -(stop.1 - start.1).abs());
+    let step = ((stop.0 - start.0).signum(), (stop.1 - start.1).signum());
+    let (mut x, mut y) = start;
+    let mut error = dx + dy;
+    loop {
+        plot(x, y);
+        if (x, y) == stop {
+            return;
+        }
+        if 2 * error >= dy {
+            error += dy;
+            x += step.0;
+        }
+        if 2 * error <= dx {
+            error += dx;
+            y += step.1;
+        }
+    }
+}
+
+/// Returns the box drawing character joining the directions of a cell of [`HTree::to_ascii`].
+///
+/// The bits of `directions` stand for up, down, left and right from the lowest bit on.
+fn box_drawing_char(directions: u8) -> char {
+    [' ', '╵', '╷', '│', '╴', '┘', '┐', '┤', '╶', '└', '┌', '├', '─', '┴', '┬', '┼'][directions as usize & 15]
+}
+
+/// Returns the distance along the Hilbert curve through a grid of `side x side` cells, `side` a power of two,
+/// of the cell in column `x` and row `y`.
+fn hilbert_index(side: u32, mut x: u32, mut y: u32) -> u64 {
+    let mut index = 0;
+    let mut quadrant_side = side / 2;
+    while quadrant_side > 0 {
+        let right = x & quadrant_side != 0;
+        let lower = y & quadrant_side != 0;
+        let quadrant = match (right, lower) {
+            (false, false) => 0,
+            (false, true) => 1,
+            (true, true) => 2,
+            (true, false) => 3,
+        };
+        index += u64::from(quadrant_side) * u64::from(quadrant_side) * quadrant;
+        // rotate the quadrant so the curve through it starts and ends next to its neighbours
+        if !lower {
+            if right {
+                x = side - 1 - x;
+                y = side - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        quadrant_side /= 2;
+    }
+    index
+}
+
+/// Scrambles `value` with a SplitMix64 step, used as a small deterministic random number generator.
+fn split_mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Maps a random `u64` to the interval `[0, 1)`.
+fn unit_interval(value: u64) -> f64 {
+    (value >> 11) as f64 / (1u64 << 53) as f64
+}
+
+impl<T> LevelCache<T> {
+    /// Returns the lines of a level in the order yielded by [`HTree::into_iter`].
+    ///
+    /// Levels beyond the order of the HTree are empty.
+    pub fn level(&self, level: usize) -> &[Line<T>] {
+        self.levels.get(level).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl<T> ParamLine<T>
+where
+    T: Float,
+{
+    /// Returns the point at parameter `t`, the start of the line for `t = 0` and its stop for `t = 1`.
+    pub fn eval(&self, t: T) -> (T, T) {
+        (self.origin.0 + self.dir.0 * t, self.origin.1 + self.dir.1 * t)
+    }
+}
+
+impl<T> HTreeIterator<T>
+where
+    T: Float,
+{
+    /// Returns up to `max` next lines and advances past them.
+    ///
+    /// Returns fewer lines once the iterator runs out, so work can be spread over calls until the
+    /// batch comes back empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let mut lines=HTree::<f32>::new(3).into_iter();
+    /// assert_eq!(lines.next_batch(10).len(),10);
+    /// assert_eq!(lines.next_batch(10).len(),5);
+    /// assert!(lines.next_batch(10).is_empty());
+    /// ```
+    pub fn next_batch(&mut self, max: usize) -> Vec<Line<T>> {
+        self.by_ref().take(max).collect()
+    }
+
+    /// Returns the current position of the iterator, see [`HTree::resume_from`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let mut lines=HTree::<f32>::new(3).into_iter();
+    /// lines.nth(4);
+    /// assert_eq!(lines.checkpoint().index,5);
+    /// ```
+    pub fn checkpoint(&self) -> IteratorCheckpoint {
+        IteratorCheckpoint {
+            index: self.index,
+            order: self.h_tree.order,
+        }
+    }
+}
+
+impl<T> Iterator for HTreeIterator<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+    fn next(&mut self) -> Option<Self::Item> {
+        self.index += 1;
+        let (order_index, iteration_index) = self.h_tree.locate_line(self.index - 1)?;
+        #[cfg(feature = "tracing")]
+        self.h_tree.trace_line(self.index, order_index, iteration_index);
+        Some(self.h_tree.segment(order_index, iteration_index))
+    }
+
+    /// Folds the remaining lines level by level without locating every line on its own.
+    ///
+    /// This also speeds up [`Iterator::for_each`] and the other consuming adapters built on top of it.
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let h_tree = self.h_tree;
+        let mut accumulator = init;
+        if h_tree.is_depth_first() {
+            for index in self.index..h_tree.len() {
+                let (order_index, iteration_index) = h_tree.locate_line(index).unwrap();
+                #[cfg(feature = "tracing")]
+                h_tree.trace_line(index + 1, order_index, iteration_index);
+                accumulator = f(accumulator, h_tree.segment(order_index, iteration_index));
+            }
+            return accumulator;
+        }
+        let Some((first_order_index, mut first_position)) = h_tree.locate(self.index) else {
+            return accumulator;
+        };
+        #[cfg(feature = "tracing")]
+        let mut index = self.index;
+        for order_index in first_order_index..=h_tree.order as u32 {
+            for position in first_position..h_tree.branches.pow(order_index) {
+                let iteration_index = h_tree.iteration_index(order_index, position);
+                #[cfg(feature = "tracing")]
+                {
+                    index += 1;
+                    h_tree.trace_line(index, order_index, iteration_index);
+                }
+                accumulator = f(accumulator, h_tree.segment(order_index, iteration_index));
+            }
+            first_position = 0;
+        }
+        accumulator
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.h_tree.len().saturating_sub(self.index);
+        (remaining, Some(remaining))
     }
 }
 
+impl<T> ExactSizeIterator for HTreeIterator<T> where T: Float {}
+
 impl<T> IntoIterator for HTree<T>
 where
     T: Float,
@@ -132,4 +3844,66 @@ where
     }
 }
 
+impl<T> IntoIterator for &HTree<T>
+where
+    T: Float,
+{
+    type Item = ((T, T), (T, T));
+    type IntoIter = HTreeIterator<T>;
+
+    /// Returns an HTreeIterator which iterates over lines of the borrowed HTree, see [`HTree::into_iter`].
+    fn into_iter(self) -> Self::IntoIter {
+        (*self).into_iter()
+    }
+}
+
+impl<T> From<HTree<T>> for Vec<((T, T), (T, T))>
+where
+    T: Float,
+{
+    /// Collects all lines of the HTree into a vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f32>=HTree::new(3);
+    /// let lines:Vec<_>=htree.into();
+    /// assert_eq!(lines.len(),15);
+    /// ```
+    fn from(h_tree: HTree<T>) -> Self {
+        h_tree.into_iter().collect()
+    }
+}
+
+/// Constructs HTrees from their order.
+pub trait HTreeExt {
+    /// Returns an instance of HTree up to the order given by `self`, see [`HTree::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTreeExt;
+    /// let htree=5usize.htree::<f64>();
+    /// assert_eq!(htree.order(),5);
+    /// ```
+    fn htree<T: Float>(self) -> HTree<T>;
+}
+
+impl HTreeExt for usize {
+    fn htree<T: Float>(self) -> HTree<T> {
+        HTree::new(self)
+    }
+}
+
+/// Constructs points from their coordinates, see [`HTree::segments_as`].
+pub trait FromXY<T> {
+    /// Returns the point at `(x, y)`.
+    fn from_xy(x: T, y: T) -> Self;
+}
 
+impl<T> FromXY<T> for (T, T) {
+    fn from_xy(x: T, y: T) -> Self {
+        (x, y)
+    }
+}
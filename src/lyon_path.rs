@@ -0,0 +1,41 @@
+//! Path events of HTrees for [lyon](https://docs.rs/lyon).
+
+use crate::HTree;
+use lyon::math::point;
+use lyon::path::PathEvent;
+use num::Float;
+
+/// Returns the lines of the HTree as lyon path events.
+///
+/// Every polyline (see [`HTree::polylines`]) becomes an open subpath made of a `Begin` event,
+/// one `Line` event per piece and an `End` event.
+///
+/// # Examples
+///
+/// ```
+/// use htree::{path_events, HTree};
+/// use lyon::path::Path;
+///
+/// let htree: HTree<f32> = HTree::new(6);
+/// let mut builder = Path::builder();
+/// for event in path_events(&htree) {
+///     builder.path_event(event);
+/// }
+/// let path = builder.build();
+/// ```
+pub fn path_events<T: Float + Into<f32>>(tree: &HTree<T>) -> impl Iterator<Item = PathEvent> {
+    tree.polylines().into_iter().flat_map(|polyline| {
+        let points: Vec<_> = polyline.into_iter().map(|(x, y)| point(x.into(), y.into())).collect();
+        let mut events = vec![PathEvent::Begin { at: points[0] }];
+        events.extend(points.windows(2).map(|pair| PathEvent::Line {
+            from: pair[0],
+            to: pair[1],
+        }));
+        events.push(PathEvent::End {
+            last: points[points.len() - 1],
+            first: points[0],
+            close: false,
+        });
+        events
+    })
+}
@@ -0,0 +1,189 @@
+//! Outer boundary and area of a union of axis aligned rectangles.
+
+use num::Float;
+use std::collections::HashMap;
+
+/// Axis aligned rectangle given by its corners `(min, max)`.
+type Rectangle<T> = ((T, T), (T, T));
+
+/// Returns the outer boundary of the union of the rectangles given by their `(min, max)` corners.
+///
+/// The plane is split into cells by all edges of the rectangles, where coordinates within `epsilon`
+/// are merged. Cells reachable from outside without crossing a rectangle are flood filled, and the
+/// boundary between them and all other cells is traced with the union on the left, preferring to
+/// turn right where rectangles only touch at a corner so such unions are traced as one ring.
+/// Holes are filled. If the union falls apart, only the ring around the part with the lowest
+/// corner is returned.
+pub(crate) fn outer_ring<T: Float>(rectangles: &[Rectangle<T>], epsilon: T) -> Vec<(T, T)> {
+    if rectangles.is_empty() {
+        return Vec::new();
+    }
+    let (xs, ys, filled) = cells(rectangles, epsilon);
+    let (width, height) = (xs.len() + 1, ys.len() + 1);
+    let mut outside = vec![false; width * height];
+    let mut stack = vec![0];
+    outside[0] = true;
+    while let Some(cell) = stack.pop() {
+        let (i, j) = (cell % width, cell / width);
+        let neighbours = [
+            (i > 0).then(|| cell - 1),
+            (i + 1 < width).then_some(cell + 1),
+            (j > 0).then(|| cell - width),
+            (j + 1 < height).then_some(cell + width),
+        ];
+        for neighbour in neighbours.into_iter().flatten() {
+            if !outside[neighbour] && !filled[neighbour] {
+                outside[neighbour] = true;
+                stack.push(neighbour);
+            }
+        }
+    }
+
+    let mut edges = boundary_edges(&outside.iter().map(|&outside| !outside).collect::<Vec<_>>(), width, height);
+    let start = *edges.keys().min_by_key(|&&(i, j)| (j, i)).unwrap();
+    trace(&mut edges, start).into_iter().map(|(i, j)| (xs[i], ys[j])).collect()
+}
+
+/// Returns all boundary rings of the union of the rectangles given by their `(min, max)` corners.
+///
+/// Cells are found and rings traced like in [`outer_ring`], but without filling holes, so every
+/// part of the union yields its outer ring and one ring per hole. With the union on the left of
+/// every ring, outer rings run counter clockwise and rings around holes clockwise with the y axis
+/// pointing up. Rings are ordered by their lowest corner.
+pub(crate) fn rings<T: Float>(rectangles: &[Rectangle<T>], epsilon: T) -> Vec<Vec<(T, T)>> {
+    if rectangles.is_empty() {
+        return Vec::new();
+    }
+    let (xs, ys, filled) = cells(rectangles, epsilon);
+    let mut edges = boundary_edges(&filled, xs.len() + 1, ys.len() + 1);
+    let mut rings = Vec::new();
+    loop {
+        let remaining = edges.iter().filter(|(_, to)| !to.is_empty()).map(|(&from, _)| from);
+        let Some(start) = remaining.min_by_key(|&(i, j)| (j, i)) else {
+            return rings;
+        };
+        rings.push(trace(&mut edges, start).into_iter().map(|(i, j)| (xs[i], ys[j])).collect());
+    }
+}
+
+/// Returns the directed edges between grid points `(i, j)` at `(xs[i], ys[j])` which separate
+/// occupied from free cells, with the occupied cells on the left.
+fn boundary_edges(occupied: &[bool], width: usize, height: usize) -> HashMap<(usize, usize), Vec<(usize, usize)>> {
+    let mut edges: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+    let mut add = |from: (usize, usize), to: (usize, usize)| edges.entry(from).or_default().push(to);
+    for j in 0..height {
+        for i in 0..width {
+            let inside = occupied[j * width + i];
+            if i + 1 < width && inside != occupied[j * width + i + 1] {
+                if inside {
+                    add((i, j - 1), (i, j));
+                } else {
+                    add((i, j), (i, j - 1));
+                }
+            }
+            if j + 1 < height && inside != occupied[(j + 1) * width + i] {
+                if inside {
+                    add((i, j), (i - 1, j));
+                } else {
+                    add((i - 1, j), (i, j));
+                }
+            }
+        }
+    }
+    edges
+}
+
+/// Removes and returns the ring of edges through `start`, given by its corners without collinear points.
+///
+/// Where several edges leave a grid point the ring turns right if possible, then goes straight on.
+fn trace(edges: &mut HashMap<(usize, usize), Vec<(usize, usize)>>, start: (usize, usize)) -> Vec<(usize, usize)> {
+    let mut ring = vec![start];
+    let mut previous = start;
+    let mut current = edges.get_mut(&start).unwrap().pop().unwrap();
+    while current != start {
+        let heading = direction(previous, current);
+        let outgoing = edges.get_mut(&current).unwrap();
+        // right turn, straight on, left turn
+        let turn = |next: &(usize, usize)| {
+            let next_heading = direction(current, *next);
+            match heading.0 * next_heading.1 - heading.1 * next_heading.0 {
+                cross if cross < 0 => 0,
+                0 => 1,
+                _ => 2,
+            }
+        };
+        let chosen = (0..outgoing.len()).min_by_key(|&index| turn(&outgoing[index])).unwrap();
+        let next = outgoing.swap_remove(chosen);
+        if turn(&next) != 1 {
+            ring.push(current);
+        }
+        previous = current;
+        current = next;
+    }
+    if direction(previous, start) == direction(start, ring[1 % ring.len()]) {
+        ring.remove(0);
+    }
+    ring
+}
+
+/// Returns the area of the union of the rectangles given by their `(min, max)` corners.
+///
+/// Overlapping parts are counted once, coordinates within `epsilon` are merged like in [`outer_ring`].
+pub(crate) fn union_area<T: Float>(rectangles: &[Rectangle<T>], epsilon: T) -> T {
+    if rectangles.is_empty() {
+        return T::zero();
+    }
+    let (xs, ys, filled) = cells(rectangles, epsilon);
+    let width = xs.len() + 1;
+    let mut area = T::zero();
+    for j in 1..ys.len() {
+        for i in 1..xs.len() {
+            if filled[j * width + i] {
+                area = area + (xs[i] - xs[i - 1]) * (ys[j] - ys[j - 1]);
+            }
+        }
+    }
+    area
+}
+
+/// Splits the plane by all edges of the rectangles and marks the cells covered by a rectangle.
+///
+/// Returns the sorted distinct x and y coordinates of the edges and the covered cells row by row.
+/// Cells are padded by a ring of empty cells, cell `(i, j)` lies between `xs[i-1]` and `xs[i]`
+/// and between `ys[j-1]` and `ys[j]`.
+fn cells<T: Float>(rectangles: &[Rectangle<T>], epsilon: T) -> (Vec<T>, Vec<T>, Vec<bool>) {
+    let xs = coordinates(rectangles.iter().flat_map(|(min, max)| [min.0, max.0]), epsilon);
+    let ys = coordinates(rectangles.iter().flat_map(|(min, max)| [min.1, max.1]), epsilon);
+    let find = |values: &[T], value: T| {
+        let index = values.partition_point(|&v| v < value - epsilon);
+        index.min(values.len() - 1)
+    };
+    let width = xs.len() + 1;
+    let mut filled = vec![false; width * (ys.len() + 1)];
+    for (min, max) in rectangles {
+        for i in find(&xs, min.0) + 1..=find(&xs, max.0) {
+            for j in find(&ys, min.1) + 1..=find(&ys, max.1) {
+                filled[j * width + i] = true;
+            }
+        }
+    }
+    (xs, ys, filled)
+}
+
+/// Returns the sorted distinct values, merging values within `epsilon` into the smallest of them.
+fn coordinates<T: Float>(values: impl Iterator<Item = T>, epsilon: T) -> Vec<T> {
+    let mut values: Vec<T> = values.collect();
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut merged: Vec<T> = Vec::with_capacity(values.len());
+    for value in values {
+        if merged.last().is_none_or(|&last| value - last > epsilon) {
+            merged.push(value);
+        }
+    }
+    merged
+}
+
+/// Returns the unit step from one grid point to a neighbouring one.
+fn direction(from: (usize, usize), to: (usize, usize)) -> (i64, i64) {
+    (to.0 as i64 - from.0 as i64, to.1 as i64 - from.1 as i64)
+}
@@ -0,0 +1,62 @@
+//! Pen paths for plotting the lines of an HTree.
+
+use crate::{graph::Graph, EmissionOrder, HTree};
+use num::Float;
+
+/// Returns the polylines a pen draws without lifting, in the order they are drawn with `order_mode`.
+///
+/// Orders of whole levels draw every line on its own. [`EmissionOrder::DepthFirst`] walks the
+/// vertex graph depth first, while [`EmissionOrder::Optimized`] draws the polylines of
+/// [`HTree::polylines`], always continuing with the nearest remaining one.
+pub(crate) fn strokes<T: Float>(h_tree: &HTree<T>, order_mode: EmissionOrder) -> Vec<Vec<(T, T)>> {
+    match order_mode {
+        EmissionOrder::AsBuilt | EmissionOrder::RowMajor | EmissionOrder::ColMajor => h_tree
+            .with_emission_order(order_mode)
+            .into_iter()
+            .map(|(start, stop)| vec![start, stop])
+            .collect(),
+        EmissionOrder::DepthFirst => {
+            let graph = Graph::new(h_tree);
+            graph
+                .depth_first()
+                .into_iter()
+                .map(|chain| chain.into_iter().map(|vertex| graph.vertices[vertex]).collect())
+                .collect()
+        }
+        EmissionOrder::Optimized => nearest_first(h_tree.polylines()),
+    }
+}
+
+/// Orders polylines greedily so every one starts at the end nearest to the end of the one before.
+///
+/// Polylines are reversed where that brings their start closer, every step searches all remaining
+/// polylines.
+fn nearest_first<T: Float>(mut remaining: Vec<Vec<(T, T)>>) -> Vec<Vec<(T, T)>> {
+    let distance = |a: (T, T), b: (T, T)| (a.0 - b.0).hypot(a.1 - b.1);
+    let mut ordered: Vec<Vec<(T, T)>> = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        let next = match ordered.last().and_then(|polyline| polyline.last()) {
+            None => 0,
+            Some(&position) => {
+                let (next, reverse) = remaining
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(index, polyline)| {
+                        [
+                            (index, false, distance(position, polyline[0])),
+                            (index, true, distance(position, polyline[polyline.len() - 1])),
+                        ]
+                    })
+                    .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap())
+                    .map(|(index, reverse, _)| (index, reverse))
+                    .unwrap();
+                if reverse {
+                    remaining[next].reverse();
+                }
+                next
+            }
+        };
+        ordered.push(remaining.swap_remove(next));
+    }
+    ordered
+}
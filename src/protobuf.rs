@@ -0,0 +1,49 @@
+//! Protobuf export of HTrees with [prost](https://docs.rs/prost).
+
+use crate::HTree;
+use prost::Message;
+
+/// Protobuf message holding all lines of an HTree.
+#[derive(Clone, PartialEq, Message)]
+pub struct HTreeSegments {
+    #[prost(message, repeated, tag = "1")]
+    pub segments: Vec<Segment>,
+}
+
+/// Protobuf message of a single line from `(x0, y0)` to `(x1, y1)`.
+#[derive(Clone, PartialEq, Message)]
+pub struct Segment {
+    #[prost(double, tag = "1")]
+    pub x0: f64,
+    #[prost(double, tag = "2")]
+    pub y0: f64,
+    #[prost(double, tag = "3")]
+    pub x1: f64,
+    #[prost(double, tag = "4")]
+    pub y1: f64,
+}
+
+/// Encodes the lines of the HTree as an [`HTreeSegments`] protobuf message.
+///
+/// Lines are stored in the order yielded by [`HTree::into_iter`]. The message corresponds to
+///
+/// ```proto
+/// message Segment { double x0 = 1; double y0 = 2; double x1 = 3; double y1 = 4; }
+/// message HTreeSegments { repeated Segment segments = 1; }
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use htree::{to_protobuf, HTree};
+/// let htree: HTree<f64> = HTree::new(6);
+/// let bytes = to_protobuf(&htree);
+/// assert!(!bytes.is_empty());
+/// ```
+pub fn to_protobuf(tree: &HTree<f64>) -> Vec<u8> {
+    let segments = tree
+        .into_iter()
+        .map(|((x0, y0), (x1, y1))| Segment { x0, y0, x1, y1 })
+        .collect();
+    HTreeSegments { segments }.encode_to_vec()
+}
@@ -0,0 +1,36 @@
+//! Drawing of HTrees with [raqote](https://docs.rs/raqote).
+
+use crate::HTree;
+use raqote::{DrawOptions, DrawTarget, PathBuilder, Source, StrokeStyle};
+
+/// Strokes the lines of the HTree onto a raqote `DrawTarget`.
+///
+/// All lines are joined into polylines (see [`HTree::polylines`]) and stroked as a single path.
+/// Coordinates are used as is, set a transform on the draw target to scale the HTree to its size.
+///
+/// # Examples
+///
+/// ```
+/// use htree::{draw_into_raqote, HTree};
+/// use raqote::{DrawTarget, SolidSource, Source, StrokeStyle, Transform};
+///
+/// let htree: HTree<f32> = HTree::new(6);
+/// let mut dt = DrawTarget::new(700, 495);
+/// dt.set_transform(&Transform::scale(700.0, 700.0));
+/// let black = Source::Solid(SolidSource::from_unpremultiplied_argb(255, 0, 0, 0));
+/// let stroke = StrokeStyle { width: 1.0 / 700.0, ..Default::default() };
+/// draw_into_raqote(&htree, &mut dt, &black, &stroke);
+/// ```
+pub fn draw_into_raqote(tree: &HTree<f32>, dt: &mut DrawTarget, source: &Source, stroke: &StrokeStyle) {
+    let mut path = PathBuilder::new();
+    for polyline in tree.polylines() {
+        let mut points = polyline.into_iter();
+        if let Some((x, y)) = points.next() {
+            path.move_to(x, y);
+        }
+        for (x, y) in points {
+            path.line_to(x, y);
+        }
+    }
+    dt.stroke(&path.finish(), source, stroke, &DrawOptions::new());
+}
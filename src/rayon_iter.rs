@@ -0,0 +1,32 @@
+//! Parallel iteration over HTrees with [rayon](https://docs.rs/rayon).
+
+use crate::{HTree, Line};
+use num::Float;
+use rayon::iter::{IntoParallelIterator, MapWith, ParallelIterator};
+
+impl<T> IntoParallelIterator for &HTree<T>
+where
+    T: Float + Send + Sync,
+{
+    type Iter = MapWith<rayon::range::Iter<usize>, HTree<T>, fn(&mut HTree<T>, usize) -> Line<T>>;
+    type Item = Line<T>;
+
+    /// Returns a parallel iterator over the lines of the HTree.
+    ///
+    /// Every line is computed on its own from its index with [`HTree::segment_at`], the lines are
+    /// in the order of [`HTree::into_iter`] when collected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// use rayon::prelude::*;
+    /// let htree:HTree<f32>=HTree::new(10);
+    /// let lines:Vec<_>=(&htree).into_par_iter().collect();
+    /// assert_eq!(lines.len(),htree.len());
+    /// ```
+    fn into_par_iter(self) -> Self::Iter {
+        let segment_at: fn(&mut HTree<T>, usize) -> Line<T> = |h_tree, index| h_tree.segment_at(index).unwrap();
+        (0..self.len()).into_par_iter().map_with(*self, segment_at)
+    }
+}
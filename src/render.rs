@@ -0,0 +1,190 @@
+//! Rendering of HTrees into images with [image](https://docs.rs/image).
+
+use crate::{distance_to_segment, HTree};
+use image::{ImageBuffer, Luma};
+use imageproc::drawing::draw_line_segment_mut;
+use num::Float;
+use std::io::Cursor;
+use std::path::Path;
+
+/// Returns a white image of the given size.
+fn blank(width: u32, height: u32) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+    let mut image: ImageBuffer<Luma<u8>, Vec<u8>> = ImageBuffer::new(width, height);
+    // white background
+    image.fill(255u8);
+    image
+}
+
+/// Draws lines of the HTree in black onto the image.
+///
+/// The HTree is scaled as large as possible without distorting it and centered within the image.
+fn draw_lines<I>(image: &mut ImageBuffer<Luma<u8>, Vec<u8>>, tree: &HTree<f32>, lines: I)
+where
+    I: IntoIterator<Item = ((f32, f32), (f32, f32))>,
+{
+    let (width, height) = (image.width() as f32, image.height() as f32);
+    let (extent_width, extent_height) = tree.extent();
+    let scale = (width / extent_width).min(height / extent_height);
+    let offset = ((width - scale * extent_width) / 2.0, (height - scale * extent_height) / 2.0);
+
+    let black = Luma([0u8]);
+    for (start, stop) in lines {
+        let (start, stop) = (tree.relative(start), tree.relative(stop));
+        draw_line_segment_mut(
+            image,
+            (offset.0 + start.0 * scale, offset.1 + start.1 * scale),
+            (offset.0 + stop.0 * scale, offset.1 + stop.1 * scale),
+            black,
+        );
+    }
+}
+
+/// Draws the lines of the HTree in black onto a white image of the given size.
+fn draw(tree: &HTree<f32>, width: u32, height: u32) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+    let mut image = blank(width, height);
+    draw_lines(&mut image, tree, *tree);
+    image
+}
+
+/// Encodes the image as PNG.
+fn encode_png(image: &ImageBuffer<Luma<u8>, Vec<u8>>) -> image::ImageResult<Vec<u8>> {
+    let mut bytes = Cursor::new(Vec::new());
+    image.write_to(&mut bytes, image::ImageOutputFormat::Png)?;
+    Ok(bytes.into_inner())
+}
+
+/// Renders the HTree in black on white into a PNG file of the given size.
+///
+/// The HTree is scaled as large as possible without distorting it and centered within the image.
+///
+/// # Examples
+///
+/// ```no_run
+/// use htree::{render_to_png, HTree};
+/// let htree: HTree<f32> = HTree::new(6);
+/// render_to_png(&htree, "htree.png", 700, 495).unwrap();
+/// ```
+pub fn render_to_png<P: AsRef<Path>>(tree: &HTree<f32>, path: P, width: u32, height: u32) -> image::ImageResult<()> {
+    draw(tree, width, height).save_with_format(path, image::ImageFormat::Png)
+}
+
+/// Renders the HTree in black on white into PNG encoded bytes of an image of the given size.
+///
+/// # Examples
+///
+/// ```
+/// use htree::{render_to_png_bytes, HTree};
+/// let htree: HTree<f32> = HTree::new(6);
+/// let png = render_to_png_bytes(&htree, 700, 495).unwrap();
+/// assert!(png.starts_with(b"\x89PNG"));
+/// ```
+pub fn render_to_png_bytes(tree: &HTree<f32>, width: u32, height: u32) -> image::ImageResult<Vec<u8>> {
+    encode_png(&draw(tree, width, height))
+}
+
+/// Renders one PNG encoded frame per level of the HTree, showing all lines up to that level.
+///
+/// The first frame only contains the root line while the last frame shows the whole HTree.
+///
+/// # Examples
+///
+/// ```
+/// use htree::{render_growth_frames, HTree};
+/// let htree: HTree<f32> = HTree::new(6);
+/// let frames = render_growth_frames(&htree, 350, 248);
+/// assert_eq!(frames.len(), 7);
+/// ```
+pub fn render_growth_frames(tree: &HTree<f32>, width: u32, height: u32) -> Vec<Vec<u8>> {
+    let mut image = blank(width, height);
+    (0..=tree.order())
+        .map(|level| {
+            draw_lines(&mut image, tree, tree.segments_between(level, level));
+            encode_png(&image).expect("encoding a PNG into memory does not fail")
+        })
+        .collect()
+}
+
+/// Renders the HTree in black on white into a thumbnail whose longer side is `max_dim` pixels long.
+///
+/// The thumbnail has the aspect of the bounding box, see [`HTree::canvas_size`]. Levels whose lines
+/// would be shorter than a pixel are left out, like [`HTree::useful_order_for_resolution`] but for
+/// the branches and bounding box of this HTree, so large HTrees render as fast as small ones.
+///
+/// # Examples
+///
+/// ```
+/// use htree::{thumbnail, HTree};
+/// let htree: HTree<f32> = HTree::new(24);
+/// let image = thumbnail(&htree, 128);
+/// assert_eq!(image.dimensions(), (128, 91));
+/// ```
+pub fn thumbnail(tree: &HTree<f32>, max_dim: u32) -> image::RgbaImage {
+    let (width, height) = tree.canvas_size(max_dim);
+    let (extent_width, extent_height) = tree.extent();
+    let scale = (width as f32 / extent_width).min(height as f32 / extent_height);
+    let mut order = 0;
+    while order < tree.order() && tree.segment_length(order as u32 + 1) * scale >= 1.0 {
+        order += 1;
+    }
+    let capped = HTree { order, ..*tree };
+    image::DynamicImage::ImageLuma8(draw(&capped, width, height)).into_rgba8()
+}
+
+impl<T> HTree<T>
+where
+    T: Float,
+{
+    /// Returns the anti-aliased coverage in `[0, 1]` of every pixel of a `width` x `height` image
+    /// by the lines of the HTree stroked `line_width` pixels wide, row by row.
+    ///
+    /// The HTree is scaled as large as possible without distorting it and centered within the image.
+    /// A pixel is covered by the fraction of a box of one pixel around its center which the closest
+    /// stroke overlaps, approximated from the distance of its center to the line. Overlapping strokes
+    /// take the largest coverage instead of adding up, so junctions are not darker than the lines.
+    /// Coverage is linear, convert it to the gamma of the target before blending in sRGB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree: HTree<f32> = HTree::new(0);
+    /// let coverage = htree.coverage_buffer(8, 7, 1.0);
+    /// assert_eq!(coverage[8 * 3 + 4], 1.0);
+    /// assert_eq!(coverage[0], 0.0);
+    /// ```
+    pub fn coverage_buffer(&self, width: u32, height: u32, line_width: f32) -> Vec<f32> {
+        let mut coverage = vec![0.0f32; width as usize * height as usize];
+        let (image_width, image_height) = (width as f32, height as f32);
+        let (extent_width, extent_height) = self.extent();
+        let (extent_width, extent_height) = (extent_width.to_f32().unwrap(), extent_height.to_f32().unwrap());
+        let scale = (image_width / extent_width).min(image_height / extent_height);
+        let offset = ((image_width - scale * extent_width) / 2.0, (image_height - scale * extent_height) / 2.0);
+        let pixel = |point: (T, T)| {
+            let (x, y) = self.relative(point);
+            (offset.0 + x.to_f32().unwrap() * scale, offset.1 + y.to_f32().unwrap() * scale)
+        };
+        // strokes reach half their width plus half a pixel beyond the line
+        let reach = line_width / 2.0 + 0.5;
+        for (start, stop) in self.into_iter() {
+            let (start, stop) = (pixel(start), pixel(stop));
+            let columns = pixel_span(start.0.min(stop.0) - reach, start.0.max(stop.0) + reach, width);
+            let rows = pixel_span(start.1.min(stop.1) - reach, start.1.max(stop.1) + reach, height);
+            for row in rows {
+                for column in columns.clone() {
+                    let center = (column as f32 + 0.5, row as f32 + 0.5);
+                    let value = (reach - distance_to_segment(center, (start, stop))).clamp(0.0, 1.0);
+                    let cell = &mut coverage[row * width as usize + column];
+                    *cell = cell.max(value);
+                }
+            }
+        }
+        coverage
+    }
+}
+
+/// Returns the pixels of an axis with `count` pixels whose centers may lie within `[low, high]`.
+fn pixel_span(low: f32, high: f32, count: u32) -> std::ops::Range<usize> {
+    let first = low.floor().max(0.0) as usize;
+    let last = (high.ceil().max(0.0) as usize).min(count as usize);
+    first..last.max(first)
+}
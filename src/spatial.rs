@@ -0,0 +1,101 @@
+//! Uniform grid over the lines of an HTree for repeated nearest line queries.
+
+use crate::{distance_to_segment, HTree, Line};
+use num::Float;
+
+/// Lines of an HTree bucketed into a uniform grid over its bounding box, see [`HTree::build_spatial_index`].
+#[derive(Clone, Debug)]
+pub struct SpatialIndex<T> {
+    lines: Vec<Line<T>>,
+    cells: usize,
+    origin: (T, T),
+    cell_size: (T, T),
+    /// Indices of the lines overlapping every cell, row by row.
+    buckets: Vec<Vec<usize>>,
+}
+
+impl<T> SpatialIndex<T>
+where
+    T: Float,
+{
+    pub(crate) fn new(h_tree: &HTree<T>, cells: u32) -> SpatialIndex<T> {
+        let cells = cells.max(1) as usize;
+        let (width, height) = h_tree.extent();
+        let count = T::from(cells).unwrap();
+        let mut index = SpatialIndex {
+            lines: h_tree.into_iter().collect(),
+            cells,
+            origin: h_tree.origin,
+            cell_size: (width / count, height / count),
+            buckets: vec![Vec::new(); cells * cells],
+        };
+        for (line, &(start, stop)) in index.lines.iter().enumerate() {
+            let min = index.cell(start.0.min(stop.0), start.1.min(stop.1));
+            let max = index.cell(start.0.max(stop.0), start.1.max(stop.1));
+            for row in min.1..=max.1 {
+                for column in min.0..=max.0 {
+                    index.buckets[row * cells + column].push(line);
+                }
+            }
+        }
+        index
+    }
+
+    /// Returns the index in the order of [`HTree::into_iter`] of the line closest to `p` and its distance to `p`.
+    ///
+    /// Cells are searched in growing rings around the cell of `p`, or the closest cell if `p` lies
+    /// outside of the bounding box, until no line in the remaining cells can be closer. Of lines at
+    /// the same distance the one yielded first is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::HTree;
+    /// let htree:HTree<f64>=HTree::new(8);
+    /// let index=htree.build_spatial_index(32);
+    /// let (nearest,distance)=index.nearest((0.31,0.42));
+    /// assert_eq!(htree.segment_at(nearest).unwrap(),htree.nearest_segment((0.31,0.42)));
+    /// assert_eq!(distance,htree.distance_to((0.31,0.42)));
+    /// ```
+    pub fn nearest(&self, p: (T, T)) -> (usize, T) {
+        let (column, row) = self.cell(p.0, p.1);
+        let mut nearest: Option<(usize, T)> = None;
+        for ring in 0..self.cells {
+            let (first_row, last_row) = (row.saturating_sub(ring), (row + ring).min(self.cells - 1));
+            let (first_column, last_column) = (column.saturating_sub(ring), (column + ring).min(self.cells - 1));
+            for r in first_row..=last_row {
+                for c in first_column..=last_column {
+                    // inner cells were searched by the previous rings
+                    if r.abs_diff(row) < ring && c.abs_diff(column) < ring {
+                        continue;
+                    }
+                    for &line in &self.buckets[r * self.cells + c] {
+                        let distance = distance_to_segment(p, self.lines[line]);
+                        let closer = nearest.is_none_or(|(best, best_distance)| {
+                            distance < best_distance || (distance == best_distance && line < best)
+                        });
+                        if closer {
+                            nearest = Some((line, distance));
+                        }
+                    }
+                }
+            }
+            // lines in cells beyond this ring are at least `ring` cells away
+            let reach = T::from(ring).unwrap() * self.cell_size.0.min(self.cell_size.1);
+            if let Some((_, distance)) = nearest {
+                if distance <= reach {
+                    break;
+                }
+            }
+        }
+        nearest.expect("an HTree has at least one line")
+    }
+
+    /// Returns the `(column, row)` of the cell containing the point, clamped to the grid.
+    fn cell(&self, x: T, y: T) -> (usize, usize) {
+        let clamp = |value: T, size: T| {
+            (value / size).floor().max(T::zero()).to_usize().unwrap_or(usize::MAX).min(self.cells - 1)
+        };
+        (clamp(x - self.origin.0, self.cell_size.0), clamp(y - self.origin.1, self.cell_size.1))
+    }
+}
@@ -0,0 +1,67 @@
+//! Export of HTrees as binary STL reliefs.
+
+use crate::HTree;
+
+/// Returns a binary STL of the lines of the HTree stroked `line_width` wide and extruded `height` high.
+///
+/// Every line becomes a box of 12 triangles standing on `z = 0` whose top and bottom are the quad
+/// of the line in [`HTree::to_line_mesh`]. Boxes overlap where lines meet, slicers merge them into
+/// one solid. Triangles are wound counterclockwise seen from
+/// outside of their box, and coordinates are those of the HTree, scale the slab in the slicer.
+///
+/// # Examples
+///
+/// ```
+/// use htree::{to_extruded_stl, HTree};
+/// let htree: HTree<f32> = HTree::new(2);
+/// let stl = to_extruded_stl(&htree, 0.02, 0.05);
+/// assert_eq!(stl.len(), 84 + 50 * 12 * 7);
+/// ```
+pub fn to_extruded_stl(tree: &HTree<f32>, line_width: f32, height: f32) -> Vec<u8> {
+    let (vertices, indices) = tree.to_line_mesh(line_width);
+    let triangles = 12 * vertices.len() / 4;
+    let mut stl = Vec::with_capacity(84 + 50 * triangles);
+    stl.extend_from_slice(&[0u8; 80]);
+    stl.extend_from_slice(&(triangles as u32).to_le_bytes());
+    for quad in indices.chunks(6) {
+        let corners = [quad[0], quad[1], quad[2], quad[5]].map(|index| vertices[index as usize]);
+        let bottom = corners.map(|(x, y)| [x, y, 0.0]);
+        let top = corners.map(|(x, y)| [x, y, height]);
+        let center = [
+            corners.iter().map(|corner| corner.0).sum::<f32>() / 4.0,
+            corners.iter().map(|corner| corner.1).sum::<f32>() / 4.0,
+            height / 2.0,
+        ];
+        let mut faces = vec![[bottom[0], bottom[1], bottom[2]], [bottom[0], bottom[2], bottom[3]]];
+        faces.extend([[top[0], top[1], top[2]], [top[0], top[2], top[3]]]);
+        for side in 0..4 {
+            let next = (side + 1) % 4;
+            faces.extend([[bottom[side], bottom[next], top[next]], [bottom[side], top[next], top[side]]]);
+        }
+        for face in faces {
+            write_triangle(&mut stl, face, center);
+        }
+    }
+    stl
+}
+
+/// Appends the triangle, wound counterclockwise seen from the side facing away from `inside`.
+fn write_triangle(stl: &mut Vec<u8>, mut triangle: [[f32; 3]; 3], inside: [f32; 3]) {
+    let edge = |from: [f32; 3], to: [f32; 3]| [to[0] - from[0], to[1] - from[1], to[2] - from[2]];
+    let (u, v) = (edge(triangle[0], triangle[1]), edge(triangle[0], triangle[2]));
+    let mut normal = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+    let outward = edge(inside, triangle[0]);
+    if normal.iter().zip(outward).map(|(a, b)| a * b).sum::<f32>() < 0.0 {
+        triangle.swap(1, 2);
+        normal = normal.map(|component| -component);
+    }
+    let length = normal.iter().map(|component| component * component).sum::<f32>().sqrt();
+    for component in normal {
+        stl.extend_from_slice(&(component / length).to_le_bytes());
+    }
+    for component in triangle.iter().flatten() {
+        stl.extend_from_slice(&component.to_le_bytes());
+    }
+    // attribute byte count
+    stl.extend_from_slice(&[0, 0]);
+}
@@ -0,0 +1,115 @@
+//! Export of HTrees as SVG documents.
+
+use crate::{half, HTree};
+use num::Float;
+use std::fmt::Write;
+
+/// Returns the SVG document of the HTree drawn into its bounding box, see [`HTree::to_svg`].
+pub(crate) fn document<T: Float>(h_tree: &HTree<T>, stroke_width: T, corner_radius: T) -> String {
+    let (width, height) = h_tree.extent();
+    let (width, height) = (number(width), number(height));
+    let (x, y) = (number(h_tree.origin.0), number(h_tree.origin.1));
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{x} {y} {width} {height}\">\
+         <path d=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"{}\"/></svg>",
+        path_data(h_tree, corner_radius),
+        number(stroke_width)
+    )
+}
+
+/// Returns an SVG document of `width` x `height` with every level of the HTree in its own layer.
+///
+/// Levels become groups labeled `level 0`, `level 1`, ... which Inkscape shows as layers, each holding
+/// one `<line>` per line of the level. The HTree is scaled as large as possible without distorting
+/// it and centered within the document.
+///
+/// # Examples
+///
+/// ```
+/// use htree::{to_svg_layered, HTree};
+/// let htree: HTree<f32> = HTree::new(3);
+/// let svg = to_svg_layered(&htree, 800.0, 566.0);
+/// assert_eq!(svg.matches("<g ").count(), 4);
+/// assert_eq!(svg.matches("<line ").count(), 15);
+/// ```
+pub fn to_svg_layered<T: Float + Into<f64>>(tree: &HTree<T>, width: f64, height: f64) -> String {
+    let (extent_width, extent_height) = tree.extent();
+    let (extent_width, extent_height) = (extent_width.into(), extent_height.into());
+    let scale = (width / extent_width).min(height / extent_height);
+    let offset = ((width - scale * extent_width) / 2.0, (height - scale * extent_height) / 2.0);
+    let pixel = |point: (T, T)| {
+        let (x, y) = tree.relative(point);
+        (offset.0 + x.into() * scale, offset.1 + y.into() * scale)
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:inkscape=\"http://www.inkscape.org/namespaces/inkscape\" \
+         width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+    );
+    for level in 0..=tree.order() {
+        let _ = writeln!(
+            svg,
+            "<g inkscape:label=\"level {level}\" inkscape:groupmode=\"layer\" stroke=\"black\" fill=\"none\">"
+        );
+        for (start, stop) in tree.level(level) {
+            let ((x1, y1), (x2, y2)) = (pixel(start), pixel(stop));
+            let _ = writeln!(svg, "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\"/>");
+        }
+        svg.push_str("</g>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Returns the path data of all lines, filleting the junctions with arcs of radius `corner_radius`.
+///
+/// At every end of a line which carries a child, the line stops `corner_radius` before the child
+/// and two quarter circles lead from there onto both halves of the child. The radius is limited
+/// to half the length of the child so fillets of the deepest levels do not run past its ends.
+fn path_data<T: Float>(h_tree: &HTree<T>, corner_radius: T) -> String {
+    let mut data = String::new();
+    for level in 0..=h_tree.order() {
+        let radius = if level < h_tree.order() {
+            corner_radius.min(h_tree.segment_length(level as u32 + 1) * half())
+        } else {
+            T::zero()
+        };
+        for (start, stop) in h_tree.level(level) {
+            if radius <= T::zero() {
+                move_to(&mut data, start);
+                line_to(&mut data, stop);
+                continue;
+            }
+            let length = (stop.0 - start.0).hypot(stop.1 - start.1);
+            let direction = ((stop.0 - start.0) / length, (stop.1 - start.1) / length);
+            let offset = (direction.0 * radius, direction.1 * radius);
+            let (first, last) = ((start.0 + offset.0, start.1 + offset.1), (stop.0 - offset.0, stop.1 - offset.1));
+            move_to(&mut data, first);
+            line_to(&mut data, last);
+            // quarter circles from the shortened ends onto both sides of the perpendicular children
+            for (from, end, outward) in [(first, start, (-offset.0, -offset.1)), (last, stop, offset)] {
+                for side in [(-offset.1, offset.0), (offset.1, -offset.0)] {
+                    let sweep = outward.0 * side.1 - outward.1 * side.0 > T::zero();
+                    move_to(&mut data, from);
+                    let to = (end.0 + side.0, end.1 + side.1);
+                    let radius = number(radius);
+                    let _ = write!(data, "A{radius} {radius} 0 0 {} {} {}", sweep as u8, number(to.0), number(to.1));
+                }
+            }
+        }
+    }
+    data
+}
+
+fn move_to<T: Float>(data: &mut String, point: (T, T)) {
+    let _ = write!(data, "M{} {}", number(point.0), number(point.1));
+}
+
+fn line_to<T: Float>(data: &mut String, point: (T, T)) {
+    let _ = write!(data, "L{} {}", number(point.0), number(point.1));
+}
+
+/// Returns the shortest decimal representation of `value`.
+fn number<T: Float>(value: T) -> f64 {
+    value.to_f64().unwrap()
+}
@@ -0,0 +1,115 @@
+//! Three dimensional H Tree whose lines cycle through the x, y and z axes.
+
+use num::Float;
+use std::marker::PhantomData;
+
+#[derive(Clone, Copy, Debug)]
+pub struct HTree3<T> {
+    order: usize,
+    _marker: PhantomData<T>,
+}
+
+pub struct HTree3Iterator<T>
+where
+    T: Float,
+{
+    h_tree: HTree3<T>,
+    index: usize,
+}
+
+impl<T> HTree3<T>
+where
+    T: Float,
+{
+    /// Returns an instance of a three dimensional HTree up to specified order.
+    ///
+    /// Lines of level `l` run along axis `l % 3`, starting with the x axis.
+    /// The lines are contained in the box `[0,1] x [0,2^(-1/3)] x [0,2^(-2/3)]` so that every line is
+    /// `2^(-1/3)` times as long as the line of the previous level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::three_d::HTree3;
+    /// let htree:HTree3<f32>=HTree3::new(10);
+    /// ```
+    pub fn new(order: usize) -> HTree3<T> {
+        HTree3 {
+            order,
+            _marker: PhantomData {},
+        }
+    }
+
+    /// Returns the line with index `iteration_index` within level `order_index`.
+    fn segment(&self, order_index: u32, iteration_index: u32) -> ((T, T, T), (T, T, T)) {
+        let num_rectangles = grid_dimensions(order_index);
+        let axis = (order_index % 3) as usize;
+        let (first, second) = ((axis + 1) % 3, (axis + 2) % 3);
+
+        // iteration_index=pair+pairs*(first+num_first*second)
+        let pairs = num_rectangles[axis] / 2;
+        let mut start = [0u32; 3];
+        start[axis] = 2 * (iteration_index % pairs);
+        start[first] = iteration_index / pairs % num_rectangles[first];
+        start[second] = iteration_index / pairs / num_rectangles[first];
+        let mut end = start;
+        end[axis] += 1;
+
+        let scale = |cells: [u32; 3]| {
+            let coordinate = |axis: usize| {
+                let size = T::from(2).unwrap().powf(T::from(-(axis as f64) / 3.0).unwrap());
+                (T::from(cells[axis]).unwrap() + T::from(0.5).unwrap()) / T::from(num_rectangles[axis]).unwrap() * size
+            };
+            (coordinate(0), coordinate(1), coordinate(2))
+        };
+        (scale(start), scale(end))
+    }
+}
+
+/// Returns the number of boxes the bounding box is split into along the x, y and z axis at a level.
+fn grid_dimensions(level: u32) -> [u32; 3] {
+    // every level splits the boxes along the axis of its lines
+    [0, 1, 2].map(|axis| 1u32 << ((level + 3 - axis) / 3))
+}
+
+impl<T> Iterator for HTree3Iterator<T>
+where
+    T: Float,
+{
+    type Item = ((T, T, T), (T, T, T));
+    fn next(&mut self) -> Option<Self::Item> {
+        self.index += 1;
+        let order_index = self.index.ilog2();
+        if order_index > self.h_tree.order as u32 {
+            return None;
+        }
+        let iteration_index = self.index as u32 - (1u32 << order_index);
+        Some(self.h_tree.segment(order_index, iteration_index))
+    }
+}
+
+impl<T> IntoIterator for HTree3<T>
+where
+    T: Float,
+{
+    type Item = ((T, T, T), (T, T, T));
+    type IntoIter = HTree3Iterator<T>;
+
+    /// Returns an HTree3Iterator which iterates over lines of the three dimensional HTree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use htree::three_d::HTree3;
+    /// let htree:HTree3<f32>=HTree3::new(6);
+    /// for (start,stop) in htree.into_iter(){
+    ///     println!("line from {start:?} to {stop:?}");
+    /// }
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        HTree3Iterator {
+            h_tree: self,
+            index: 0,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use htree::{HTree, MAX_SUPPORTED_ORDER};
+
+#[test]
+fn test_arbitrary_trees_are_valid() {
+    let bytes: Vec<u8> = (0..=255).collect();
+    let first = HTree::<f64>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+    let second = HTree::<f64>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+    assert!(first.into_iter().take(100).eq(second.into_iter().take(100)));
+    assert_eq!((first.order(), first.branches()), (second.order(), second.branches()));
+
+    for offset in 0..200 {
+        let h_tree = HTree::<f32>::arbitrary(&mut Unstructured::new(&bytes[offset..])).unwrap();
+        assert!(h_tree.order() <= MAX_SUPPORTED_ORDER);
+        assert!((2..=4).contains(&h_tree.branches()));
+        assert!((h_tree.branches() as u64).pow(h_tree.order() as u32 + 1) <= u32::MAX as u64);
+        assert!(h_tree.segment_at(0).is_some());
+    }
+    // running out of data still gives a valid tree
+    let h_tree = HTree::<f32>::arbitrary(&mut Unstructured::new(&[])).unwrap();
+    assert_eq!((h_tree.order(), h_tree.branches()), (0, 2));
+}
@@ -0,0 +1,18 @@
+use htree::{to_bezier_path, HTree};
+
+#[test]
+fn test_bezier_curves_follow_the_lines() {
+    let htree: HTree<f32> = HTree::with_branching(4, 3);
+    let curves = to_bezier_path(&htree);
+    assert_eq!(curves.len(), htree.len());
+    for (curve, (start, stop)) in curves.iter().zip(&htree) {
+        let (start, stop) = ((start.0 as f64, start.1 as f64), (stop.0 as f64, stop.1 as f64));
+        assert_eq!(curve.eval(0.0), start);
+        let end = curve.eval(1.0);
+        assert!((end.0 - stop.0).abs() < 1e-12 && (end.1 - stop.1).abs() < 1e-12);
+        for control in [curve.control1, curve.control2] {
+            let cross = (stop.0 - start.0) * (control.1 - start.1) - (stop.1 - start.1) * (control.0 - start.0);
+            assert!(cross.abs() < 1e-12);
+        }
+    }
+}
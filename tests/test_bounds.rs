@@ -0,0 +1,41 @@
+use htree::{is_order_supported, HTree, HTreeError, MAX_SUPPORTED_ORDER};
+
+#[test]
+fn test_max_supported_order() {
+    assert!(is_order_supported(0));
+    assert!(is_order_supported(MAX_SUPPORTED_ORDER));
+    assert!(!is_order_supported(MAX_SUPPORTED_ORDER + 1));
+}
+
+#[test]
+fn test_validated_new() {
+    assert!(HTree::<f32>::validated_new(MAX_SUPPORTED_ORDER).is_ok());
+    assert_eq!(
+        HTree::<f32>::validated_new(MAX_SUPPORTED_ORDER + 1).unwrap_err(),
+        HTreeError::UnsupportedOrder(MAX_SUPPORTED_ORDER + 1)
+    );
+    assert!(HTree::<f32>::validated_new(usize::MAX).is_err());
+}
+
+#[test]
+fn test_validated_new_iterates_like_new() {
+    // the checks of `next` are debug assertions, the output must not depend on them
+    for order in 0..10 {
+        let validated: Vec<_> = HTree::<f64>::validated_new(order).unwrap().into_iter().collect();
+        let unchecked: Vec<_> = HTree::<f64>::new(order).into_iter().collect();
+        assert_eq!(validated, unchecked);
+        assert_eq!(validated.len(), (1 << (order + 1)) - 1);
+    }
+}
+
+#[test]
+fn test_checked_len() {
+    let supported = HTree::<f32>::new(MAX_SUPPORTED_ORDER);
+    assert_eq!(supported.checked_len(), Some(supported.len()));
+    // 2^(order+1) - 1 lines
+    let bits = usize::BITS as usize;
+    assert_eq!(HTree::<f32>::new(bits - 1).checked_len(), Some(usize::MAX));
+    assert_eq!(HTree::<f32>::new(bits).checked_len(), None);
+    assert_eq!(HTree::<f32>::new(usize::MAX).checked_len(), None);
+    assert_eq!(HTree::<f32>::with_branching(8, 3).checked_len(), Some((3usize.pow(9) - 1) / 2));
+}
@@ -0,0 +1,64 @@
+use htree::HTree;
+
+#[test]
+fn test_binary_branching_is_classic() {
+    for order in 0..10 {
+        let classic: Vec<_> = HTree::<f64>::new(order).into_iter().collect();
+        let binary: Vec<_> = HTree::<f64>::with_branching(order, 2).into_iter().collect();
+        assert_eq!(classic, binary);
+    }
+}
+
+#[test]
+fn test_higher_branching_has_more_lines_per_level() {
+    let order = 4;
+    for branches in [3u32, 4] {
+        let htree: HTree<f64> = HTree::with_branching(order, branches);
+        let report = htree.growth_report();
+        let expected: Vec<usize> = (0..=order as u32).map(|level| branches.pow(level) as usize).collect();
+        assert_eq!(report.segments_per_level, expected);
+        assert_eq!(htree.into_iter().count(), expected.iter().sum::<usize>());
+        let binary = HTree::<f64>::new(order).growth_report().segments_per_level;
+        for (count, binary_count) in report.segments_per_level.iter().zip(binary).skip(1) {
+            assert!(*count > binary_count);
+        }
+    }
+}
+
+#[test]
+fn test_children_are_centered_on_parent() {
+    let htree: HTree<f64> = HTree::with_branching(3, 3);
+    let lines: Vec<_> = htree.into_iter().collect();
+    // every line of the next level is centered on a line of the previous level
+    let mut first = 0;
+    let mut count = 1;
+    while first + count < lines.len() {
+        let (parents, children) = (&lines[first..first + count], &lines[first + count..first + 4 * count]);
+        for (start, stop) in children {
+            let middle = ((start.0 + stop.0) / 2.0, (start.1 + stop.1) / 2.0);
+            assert!(parents.iter().any(|(a, b)| {
+                let cross = (b.0 - a.0) * (middle.1 - a.1) - (b.1 - a.1) * (middle.0 - a.0);
+                let inside = (middle.0 - a.0) * (middle.0 - b.0) + (middle.1 - a.1) * (middle.1 - b.1);
+                cross.abs() < 1e-12 && inside <= 1e-12
+            }));
+        }
+        first += count;
+        count *= 3;
+    }
+}
+
+#[test]
+fn test_branching_segment_lengths() {
+    let htree: HTree<f64> = HTree::with_branching(6, 3);
+    let report = htree.growth_report();
+    let lines: Vec<_> = htree.into_iter().collect();
+    let mut first = 0;
+    for (count, expected) in report.segments_per_level.iter().zip(report.segment_lengths.iter()) {
+        for (start, stop) in &lines[first..first + count] {
+            assert!(((stop.0 - start.0).hypot(stop.1 - start.1) - expected).abs() < 1e-12);
+        }
+        first += count;
+    }
+    let ratio = report.segment_lengths[1] / report.segment_lengths[0];
+    assert!((ratio - report.scaling_ratio).abs() < 1e-12);
+}
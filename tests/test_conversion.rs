@@ -0,0 +1,187 @@
+use htree::{EmissionOrder, HTree};
+
+#[test]
+fn test_vec_from_htree_matches_collect() {
+    let htree: HTree<f32> = HTree::new(7);
+    let from: Vec<((f32, f32), (f32, f32))> = htree.into();
+    let collected: Vec<((f32, f32), (f32, f32))> = htree.into_iter().collect();
+    assert_eq!(from, collected);
+}
+
+#[test]
+fn test_htree_ext() {
+    use htree::HTreeExt;
+    let htree = 5usize.htree::<f64>();
+    assert_eq!(htree.order(), 5);
+    assert!(htree.into_iter().eq(HTree::<f64>::new(5)));
+}
+
+#[test]
+fn test_extend_into_appends() {
+    let htree: HTree<f32> = HTree::new(6);
+    let prior = ((9.0, 9.0), (8.0, 8.0));
+    let mut out = vec![prior; 3];
+    htree.extend_into(&mut out);
+    assert_eq!(out.len(), 3 + htree.len());
+    assert_eq!(out[..3], [prior; 3]);
+    assert!(out[3..].iter().copied().eq(htree));
+}
+
+#[test]
+fn test_segments_as_custom_point() {
+    use htree::FromXY;
+
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    impl FromXY<f64> for Point {
+        fn from_xy(x: f64, y: f64) -> Self {
+            Point { x, y }
+        }
+    }
+
+    let htree: HTree<f64> = HTree::new(5);
+    for ((start, stop), (a, b)) in htree.segments_as::<Point>().zip(htree) {
+        assert_eq!((start.x, start.y), a);
+        assert_eq!((stop.x, stop.y), b);
+    }
+    assert!(htree.segments_as::<(f64, f64)>().eq(htree));
+}
+
+#[test]
+fn test_memory_estimate_matches_collect() {
+    // the exact size hint lets collect allocate exactly, apart from the minimal capacity of small vectors
+    for order in [2, 5, 12] {
+        let lines: Vec<_> = HTree::<f32>::new(order).into_iter().collect();
+        let bytes = lines.capacity() * std::mem::size_of::<((f32, f32), (f32, f32))>();
+        assert_eq!(HTree::<f32>::memory_estimate(order), bytes);
+        assert_eq!(HTree::<f64>::memory_estimate(order), 2 * bytes);
+    }
+}
+
+#[test]
+fn test_geometry_diff() {
+    use htree::{geometry_diff, GeometryDiff};
+    let htree: HTree<f64> = HTree::new(6);
+    let same = geometry_diff(&htree, &htree, 1e-9);
+    assert_eq!(same, GeometryDiff { only_in_a: 0, only_in_b: 0, matching: htree.len() });
+
+    let deeper: HTree<f64> = HTree::new(8);
+    let diff = geometry_diff(&htree, &deeper, 1e-9);
+    assert_eq!(diff, GeometryDiff { only_in_a: 0, only_in_b: 128 + 256, matching: htree.len() });
+
+    let square = htree.with_shape(htree::Shape::Square);
+    let diff = geometry_diff(&htree, &square, 1e-9);
+    assert_eq!(diff.only_in_a, diff.only_in_b);
+    assert!(diff.only_in_a > 0);
+}
+
+#[test]
+fn test_next_batch_reconstructs_lines() {
+    let htree: HTree<f32> = HTree::new(9);
+    let mut lines = htree.into_iter();
+    let mut batched = Vec::new();
+    loop {
+        let batch = lines.next_batch(100);
+        if batch.is_empty() {
+            break;
+        }
+        assert!(batch.len() <= 100);
+        batched.extend(batch);
+    }
+    assert_eq!(batched, htree.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_relative_segments_reconstruct_lines() {
+    let htree: HTree<f64> = HTree::new(8);
+    for ((start, delta), (expected_start, expected_stop)) in htree.relative_segments().zip(htree) {
+        assert_eq!(start, expected_start);
+        let stop = (start.0 + delta.0, start.1 + delta.1);
+        assert!((stop.0 - expected_stop.0).abs() < 1e-12 && (stop.1 - expected_stop.1).abs() < 1e-12);
+    }
+
+    let mut position = (0.0, 0.0);
+    let mut count = 0;
+    for ((travel, delta), (expected_start, expected_stop)) in htree.chained_segments().zip(htree) {
+        let start = (position.0 + travel.0, position.1 + travel.1);
+        position = (start.0 + delta.0, start.1 + delta.1);
+        assert!((start.0 - expected_start.0).abs() < 1e-9 && (start.1 - expected_start.1).abs() < 1e-9);
+        assert!((position.0 - expected_stop.0).abs() < 1e-9 && (position.1 - expected_stop.1).abs() < 1e-9);
+        count += 1;
+    }
+    assert_eq!(count, htree.len());
+}
+
+#[test]
+fn test_resume_from_checkpoint() {
+    let htree: HTree<f64> = HTree::new(8).with_emission_order(htree::EmissionOrder::DepthFirst);
+    for taken in [0, 1, 100, htree.len()] {
+        let mut original = htree.into_iter();
+        original.by_ref().take(taken).for_each(drop);
+        let resumed = htree.resume_from(original.checkpoint());
+        assert_eq!(resumed.len(), htree.len() - taken);
+        assert!(resumed.eq(original));
+    }
+}
+
+#[test]
+#[should_panic(expected = "order 8")]
+fn test_resume_from_checks_order() {
+    let checkpoint = HTree::<f64>::new(8).into_iter().checkpoint();
+    HTree::<f64>::new(7).resume_from(checkpoint);
+}
+
+#[test]
+fn test_as_f64_quads() {
+    let htree: HTree<f32> = HTree::new(7);
+    let quads = htree.as_f64_quads();
+    assert_eq!(quads.len(), htree.len());
+    for (quad, (start, stop)) in quads.iter().zip(htree) {
+        assert_eq!(*quad, [start.0 as f64, start.1 as f64, stop.0 as f64, stop.1 as f64]);
+    }
+    // records are contiguous
+    let flat: &[f64] = quads.as_flattened();
+    assert_eq!(flat[4..8], quads[1]);
+}
+
+#[test]
+fn test_write_csv_round_trip() {
+    let htree: HTree<f64> = HTree::new(6);
+    let mut buffer = Vec::new();
+    htree::write_csv(&htree, &mut buffer).unwrap();
+    let csv = String::from_utf8(buffer).unwrap();
+    let mut rows = csv.lines();
+    assert_eq!(rows.next(), Some("x0,y0,x1,y1"));
+    let lines: Vec<((f64, f64), (f64, f64))> = rows
+        .map(|row| {
+            let values: Vec<f64> = row.split(',').map(|value| value.parse().unwrap()).collect();
+            ((values[0], values[1]), (values[2], values[3]))
+        })
+        .collect();
+    assert_eq!(lines.len(), htree.len());
+    assert_eq!(lines[0], htree.into_iter().next().unwrap());
+    assert_eq!(lines, htree.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_iterator_reports_its_exact_length() {
+    for order in 0..=16 {
+        let htree: HTree<f32> = HTree::new(order);
+        let lines = htree.into_iter();
+        assert_eq!(lines.size_hint(), (htree.len(), Some(htree.len())));
+        assert_eq!(lines.len(), htree.len());
+        assert_eq!(htree.into_iter().count(), htree.len());
+    }
+
+    let mut lines = HTree::<f64>::new(3).with_emission_order(EmissionOrder::DepthFirst).into_iter();
+    for remaining in (0..15).rev() {
+        assert!(lines.next().is_some());
+        assert_eq!(lines.len(), remaining);
+    }
+    assert_eq!(lines.next(), None);
+    assert_eq!(lines.size_hint(), (0, Some(0)));
+}
@@ -0,0 +1,199 @@
+use htree::{EmissionOrder, HTree, Shape};
+
+type Line = ((f64, f64), (f64, f64));
+
+fn midpoint(line: &Line) -> (f64, f64) {
+    ((line.0 .0 + line.1 .0) / 2.0, (line.0 .1 + line.1 .1) / 2.0)
+}
+
+fn levels(lines: &[Line], order: usize) -> Vec<&[Line]> {
+    (0..=order).map(|level| &lines[(1 << level) - 1..(1 << (level + 1)) - 1]).collect()
+}
+
+#[test]
+fn test_as_built_matches_default() {
+    let htree: HTree<f64> = HTree::new(7);
+    let default: Vec<_> = htree.into_iter().collect();
+    let as_built: Vec<_> = htree.with_emission_order(EmissionOrder::AsBuilt).into_iter().collect();
+    assert_eq!(default, as_built);
+}
+
+#[test]
+fn test_row_and_col_major() {
+    let order = 7;
+    let htree: HTree<f64> = HTree::new(order);
+    let mut expected: Vec<_> = htree.into_iter().collect();
+
+    let row_major: Vec<_> = htree.with_emission_order(EmissionOrder::RowMajor).into_iter().collect();
+    let col_major: Vec<_> = htree.with_emission_order(EmissionOrder::ColMajor).into_iter().collect();
+    for (level, lines) in levels(&row_major, order).into_iter().enumerate() {
+        let keys: Vec<_> = lines.iter().map(|line| (midpoint(line).1, midpoint(line).0)).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]), "level {level}");
+    }
+    for (level, lines) in levels(&col_major, order).into_iter().enumerate() {
+        let keys: Vec<_> = lines.iter().map(midpoint).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]), "level {level}");
+    }
+
+    // only the order within each level changes
+    let sort = |lines: &mut Vec<Line>| lines.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let (mut row_major, mut col_major) = (row_major, col_major);
+    sort(&mut expected);
+    sort(&mut row_major);
+    sort(&mut col_major);
+    assert_eq!(row_major, expected);
+    assert_eq!(col_major, expected);
+}
+
+#[test]
+fn test_depth_first_keeps_subtrees_together() {
+    let htree: HTree<f64> = HTree::new(2).with_emission_order(EmissionOrder::DepthFirst);
+    let parents: Vec<_> = htree.iter_with_parent().map(|(parent, _, _)| parent).collect();
+    assert_eq!(parents, vec![None, Some(0), Some(1), Some(1), Some(0), Some(4), Some(4)]);
+
+    for branches in [2, 3, 4] {
+        let htree: HTree<f64> = HTree::with_branching(5, branches);
+        let depth_first = htree.with_emission_order(EmissionOrder::DepthFirst);
+        let lines: Vec<_> = depth_first.into_iter().collect();
+        for (parent, index, line) in depth_first.iter_with_parent() {
+            assert_eq!(depth_first.segment_at(index), Some(line));
+            if let Some(parent) = parent {
+                // children follow their parent and are centered on it
+                assert!(parent < index);
+                let (start, stop) = lines[parent];
+                let center = midpoint(&line);
+                let across = (center.0 - start.0) * (stop.1 - start.1) - (center.1 - start.1) * (stop.0 - start.0);
+                assert!(across.abs() < 1e-12);
+            }
+        }
+
+        // only the order changes
+        let sort = |mut lines: Vec<Line>| {
+            lines.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            lines
+        };
+        assert_eq!(sort(lines), sort(htree.into_iter().collect()));
+        assert_eq!(
+            sort(depth_first.segments_between(2, 3).collect()),
+            sort(htree.segments_between(2, 3).collect())
+        );
+    }
+}
+
+#[test]
+fn test_plot_report() {
+    let htree: HTree<f64> = HTree::new(8);
+    let naive = htree.plot_report(EmissionOrder::AsBuilt);
+    let depth_first = htree.plot_report(EmissionOrder::DepthFirst);
+    let optimized = htree.plot_report(EmissionOrder::Optimized);
+    assert!(optimized.travel_length <= naive.travel_length);
+    // every junction and tip ends a stroke, so no order lifts the pen less than once per line
+    assert_eq!(naive.pen_lifts, htree.len() - 1);
+    assert!(depth_first.pen_lifts >= naive.pen_lifts && optimized.pen_lifts >= naive.pen_lifts);
+    for report in [&depth_first, &optimized] {
+        assert!((report.draw_length - naive.draw_length).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_for_each_matches_next() {
+    let orders = [
+        EmissionOrder::AsBuilt,
+        EmissionOrder::RowMajor,
+        EmissionOrder::ColMajor,
+        EmissionOrder::DepthFirst,
+        EmissionOrder::Optimized,
+    ];
+    for emission_order in orders {
+        for htree in [HTree::<f64>::new(7), HTree::with_branching(4, 3)] {
+            let htree = htree.with_emission_order(emission_order);
+            let expected: Vec<Line> = (0..htree.len()).map(|index| htree.segment_at(index).unwrap()).collect();
+            for skip in [0, 1, 6, 40, expected.len()] {
+                let mut iter = htree.into_iter();
+                for _ in 0..skip {
+                    iter.next();
+                }
+                let mut lines: Vec<Line> = Vec::new();
+                iter.for_each(|line| lines.push(line));
+                assert_eq!(lines, expected[skip..], "{emission_order:?} skipping {skip}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_pen_up_count() {
+    let htree: HTree<f64> = HTree::new(6);
+    let naive = htree.pen_up_count(EmissionOrder::AsBuilt);
+    let depth_first = htree.pen_up_count(EmissionOrder::DepthFirst);
+    // only the moves between levels can touch
+    assert!(naive >= htree.len() - 1 - htree.order());
+    assert!(naive > depth_first);
+    assert_eq!(htree.pen_up_count(EmissionOrder::Optimized), depth_first);
+    // the emission order of the tree itself does not matter
+    let row_major = htree.with_emission_order(EmissionOrder::RowMajor);
+    assert_eq!(row_major.pen_up_count(EmissionOrder::DepthFirst), depth_first);
+
+    // a root line with its children forms a single connected chain up to one sibling move
+    assert_eq!(HTree::<f64>::new(0).pen_up_count(EmissionOrder::DepthFirst), 0);
+    assert_eq!(HTree::<f64>::new(1).pen_up_count(EmissionOrder::DepthFirst), 1);
+}
+
+#[test]
+fn test_ids_are_stable_across_orders() {
+    for emission_order in [EmissionOrder::AsBuilt, EmissionOrder::RowMajor, EmissionOrder::DepthFirst] {
+        let smaller: HTree<f64> = HTree::new(5).with_emission_order(emission_order);
+        let larger: HTree<f64> = HTree::new(6).with_emission_order(emission_order);
+        let ids: std::collections::HashMap<u64, Line> = larger.iter_with_id().collect();
+        assert_eq!(ids.len(), larger.len());
+        for (id, line) in smaller.iter_with_id() {
+            assert_eq!(ids[&id], line, "{emission_order:?}");
+        }
+    }
+}
+
+#[test]
+fn test_deepest_level_matches_level() {
+    let orders = [
+        EmissionOrder::AsBuilt,
+        EmissionOrder::RowMajor,
+        EmissionOrder::ColMajor,
+        EmissionOrder::DepthFirst,
+        EmissionOrder::Optimized,
+    ];
+    for emission_order in orders {
+        for htree in [HTree::<f64>::new(7), HTree::with_branching(3, 3), HTree::new(0)] {
+            let htree = htree.with_emission_order(emission_order);
+            let deepest: Vec<Line> = htree.deepest_level().collect();
+            assert_eq!(deepest, htree.level(htree.order()).collect::<Vec<_>>(), "{emission_order:?}");
+            assert_eq!(Some(&deepest.len()), htree.growth_report().segments_per_level.last());
+            let shortest = htree.into_iter().map(|(a, b)| (b.0 - a.0).hypot(b.1 - a.1)).fold(f64::MAX, f64::min);
+            for (a, b) in deepest {
+                assert!(((b.0 - a.0).hypot(b.1 - a.1) - shortest).abs() < 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_collect_all_matches_the_iterator() {
+    let orders = [
+        EmissionOrder::AsBuilt,
+        EmissionOrder::RowMajor,
+        EmissionOrder::ColMajor,
+        EmissionOrder::DepthFirst,
+        EmissionOrder::Optimized,
+    ];
+    for branches in 2..=4 {
+        for order in orders {
+            let htree: HTree<f32> = HTree::with_branching(5, branches).with_emission_order(order);
+            assert_eq!(htree.collect_all(), htree.into_iter().collect::<Vec<_>>());
+        }
+    }
+    for shape in [Shape::Classic, Shape::Square] {
+        let htree: HTree<f64> = HTree::new(9).with_shape(shape);
+        assert_eq!(htree.collect_all(), htree.into_iter().collect::<Vec<_>>());
+    }
+    let htree: HTree<f64> = HTree::with_size(7, 640.0, 480.0);
+    assert_eq!(htree.collect_all(), htree.into_iter().collect::<Vec<_>>());
+}
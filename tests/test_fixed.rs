@@ -0,0 +1,28 @@
+use htree::fixed::{HTreeFixed, FRACTION_BITS};
+use htree::HTree;
+
+fn bytes(h_tree: HTreeFixed) -> Vec<u8> {
+    h_tree
+        .into_iter()
+        .flat_map(|((a, b), (c, d))| [a, b, c, d])
+        .flat_map(i64::to_le_bytes)
+        .collect()
+}
+
+#[test]
+fn test_runs_are_identical() {
+    assert_eq!(bytes(HTreeFixed::new(12)), bytes(HTreeFixed::new(12)));
+}
+
+#[test]
+fn test_matches_floating_point() {
+    let order = 12;
+    let fixed: Vec<_> = HTreeFixed::new(order).lines_f64().collect();
+    let floating: Vec<_> = HTree::<f64>::new(order).into_iter().collect();
+    assert_eq!(fixed.len(), floating.len());
+    let tolerance = 1.0 / (1u64 << FRACTION_BITS) as f64;
+    for (((a, b), (c, d)), ((e, f), (g, h))) in fixed.into_iter().zip(floating) {
+        assert_eq!((a, c), (e, g));
+        assert!((b - f).abs() <= tolerance && (d - h).abs() <= tolerance);
+    }
+}
@@ -0,0 +1,983 @@
+use htree::{EmissionOrder, HTree, Line, Shape, Symmetries};
+
+fn cross(a: (f64, f64), b: (f64, f64)) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+#[test]
+fn test_ribbons_are_offset_lines() {
+    let htree: HTree<f64> = HTree::new(6);
+    let width = 0.01;
+    for ([a, b, c, d], (start, stop)) in htree.ribbons(width).zip(htree) {
+        let direction = (stop.0 - start.0, stop.1 - start.1);
+        let length = direction.0.hypot(direction.1);
+
+        // both long edges are parallel to the line
+        assert!(cross((b.0 - a.0, b.1 - a.1), direction).abs() < 1e-12);
+        assert!(cross((c.0 - d.0, c.1 - d.1), direction).abs() < 1e-12);
+
+        // and lie `width` apart
+        let distance = cross((d.0 - a.0, d.1 - a.1), direction).abs() / length;
+        assert!((distance - width).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_segments_in_rect_matches_brute_force() {
+    let htree: HTree<f64> = HTree::new(6);
+    let all: Vec<_> = htree.into_iter().collect();
+    let queries = [
+        ((0.0, 0.0), (1.0, 1.0)),
+        ((0.1, 0.05), (0.4, 0.3)),
+        ((0.5, 0.3535), (0.52, 0.36)),
+        ((0.25, 0.0), (0.25, 0.7)),
+        ((2.0, 2.0), (3.0, 3.0)),
+    ];
+    for (min, max) in queries {
+        let culled: Vec<_> = htree.segments_in_rect(min, max).collect();
+        let expected: Vec<_> = all
+            .iter()
+            .copied()
+            .filter(|(start, stop)| {
+                start.0.min(stop.0) <= max.0
+                    && start.0.max(stop.0) >= min.0
+                    && start.1.min(stop.1) <= max.1
+                    && start.1.max(stop.1) >= min.1
+            })
+            .collect();
+        assert!(culled.iter().all(|segment| all.contains(segment)));
+        assert_eq!(culled, expected);
+    }
+}
+
+#[test]
+fn test_debug_level_grid() {
+    let htree: HTree<f32> = HTree::new(6);
+    let expected = [(2, 1), (2, 2), (4, 2), (4, 4), (8, 4), (8, 8), (16, 8)];
+    for (level, grid) in expected.into_iter().enumerate() {
+        assert_eq!(htree.debug_level_grid(level), grid);
+    }
+}
+
+#[test]
+fn test_reversed_segments_twice_is_identity() {
+    let htree: HTree<f64> = HTree::new(6);
+    let twice: Vec<_> = htree.reversed_segments().map(|(stop, start)| (start, stop)).collect();
+    let original: Vec<_> = htree.into_iter().collect();
+    assert_eq!(twice, original);
+}
+
+#[test]
+fn test_enclosing_circle_contains_endpoints() {
+    let htree: HTree<f64> = HTree::new(7);
+    let (center, radius) = htree.enclosing_circle();
+    let mut touching = 0;
+    for point in htree.into_iter().flat_map(|(start, stop)| [start, stop]) {
+        let distance = (point.0 - center.0).hypot(point.1 - center.1);
+        assert!(distance <= radius + 1e-12);
+        if (distance - radius).abs() < 1e-12 {
+            touching += 1;
+        }
+    }
+    // the circle is tight
+    assert!(touching >= 2);
+}
+
+#[test]
+fn test_enclosing_circle_of_varied_tree_is_smallest() {
+    let htree: HTree<f64> = HTree::with_length_variation(2, 0.4, 4);
+    let (center, radius) = htree.enclosing_circle();
+    let points: Vec<_> = htree.into_iter().flat_map(|(start, stop)| [start, stop]).collect();
+    let farthest = |center: (f64, f64)| {
+        points.iter().map(|point| (point.0 - center.0).hypot(point.1 - center.1)).fold(0.0, f64::max)
+    };
+    let encloses = |center: (f64, f64), radius: f64| farthest(center) <= radius + 1e-12;
+    assert!(encloses(center, radius));
+
+    // the varied lines are not symmetric, the circle about the center of their extent is larger
+    let (min, max) = points.iter().fold(((f64::MAX, f64::MAX), (f64::MIN, f64::MIN)), |(min, max), p| {
+        ((min.0.min(p.0), min.1.min(p.1)), (max.0.max(p.0), max.1.max(p.1)))
+    });
+    assert!(farthest(((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0)) > radius + 1e-3);
+
+    // the smallest circle passes through 2 points as its diameter or through 3 points
+    let mut smallest = f64::INFINITY;
+    for (i, &a) in points.iter().enumerate() {
+        for (j, &b) in points.iter().enumerate().skip(i + 1) {
+            let middle = ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+            let candidate = (a.0 - middle.0).hypot(a.1 - middle.1);
+            if candidate < smallest && encloses(middle, candidate) {
+                smallest = candidate;
+            }
+            for &c in &points[j + 1..] {
+                let (b, c) = ((b.0 - a.0, b.1 - a.1), (c.0 - a.0, c.1 - a.1));
+                let determinant = 2.0 * (b.0 * c.1 - b.1 * c.0);
+                if determinant.abs() < 1e-12 {
+                    continue;
+                }
+                let (b_squared, c_squared) = (b.0 * b.0 + b.1 * b.1, c.0 * c.0 + c.1 * c.1);
+                let offset = (
+                    (c.1 * b_squared - b.1 * c_squared) / determinant,
+                    (b.0 * c_squared - c.0 * b_squared) / determinant,
+                );
+                let candidate = offset.0.hypot(offset.1);
+                if candidate < smallest && encloses((a.0 + offset.0, a.1 + offset.1), candidate) {
+                    smallest = candidate;
+                }
+            }
+        }
+    }
+    assert!((radius - smallest).abs() < 1e-9, "{radius} != {smallest}");
+}
+
+#[test]
+fn test_jittered_is_reproducible_and_bounded() {
+    let htree: HTree<f64> = HTree::new(6);
+    let amplitude = 0.003;
+    let a: Vec<_> = htree.jittered(amplitude, 7).collect();
+    let b: Vec<_> = htree.jittered(amplitude, 7).collect();
+    let c: Vec<_> = htree.jittered(amplitude, 8).collect();
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+
+    for (jittered, original) in a.iter().zip(htree) {
+        for (p, q) in [(jittered.0, original.0), (jittered.1, original.1)] {
+            assert!((p.0 - q.0).hypot(p.1 - q.1) <= amplitude + 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_snapped_coordinates() {
+    let htree: HTree<f64> = HTree::new(8);
+    for decimals in [0, 1, 3, 5] {
+        let factor = 10f64.powi(decimals as i32);
+        for (start, stop) in htree.snapped(decimals) {
+            for coordinate in [start.0, start.1, stop.0, stop.1] {
+                let scaled = coordinate * factor;
+                assert!((scaled - scaled.round()).abs() < 1e-6);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_motion_segments_reconstruct_lines() {
+    let htree: HTree<f64> = HTree::new(7);
+    let motions: Vec<_> = htree.motion_segments().collect();
+    assert_eq!(motions.len(), htree.into_iter().count());
+    for ((start, direction, length), (expected_start, expected_stop)) in motions.into_iter().zip(htree) {
+        assert_eq!(start, expected_start);
+        assert!((direction.0.hypot(direction.1) - 1.0).abs() < 1e-12);
+        let stop = (start.0 + direction.0 * length, start.1 + direction.1 * length);
+        assert!((stop.0 - expected_stop.0).abs() < 1e-12);
+        assert!((stop.1 - expected_stop.1).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_segments_between() {
+    let htree: HTree<f64> = HTree::new(6);
+    let lines: Vec<_> = htree.into_iter().collect();
+    assert_eq!(htree.segments_between(0, 6).collect::<Vec<_>>(), lines);
+    assert_eq!(htree.segments_between(2, 3).collect::<Vec<_>>(), lines[3..15].to_vec());
+    assert_eq!(htree.segments_between(6, 9).count(), 64);
+    assert_eq!(htree.segments_between(3, 2).count(), 0);
+    assert_eq!(htree.segments_between(7, 9).count(), 0);
+}
+
+#[test]
+fn test_ndc_iter_letterboxes() {
+    let htree: HTree<f64> = HTree::new(7);
+    let extent = |points: Vec<(f64, f64)>| {
+        let (mut min, mut max) = ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY));
+        for (x, y) in points {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+        (max.0 - min.0, max.1 - min.1)
+    };
+    let ndc: Vec<_> = htree.ndc_iter().flat_map(|(start, stop)| [start, stop]).collect();
+    for &(x, y) in &ndc {
+        assert!((-1.0..=1.0).contains(&x) && (-1.0..=1.0).contains(&y));
+    }
+
+    let (width, height) = extent(ndc);
+    let (original_width, original_height) = extent(htree.into_iter().flat_map(|(start, stop)| [start, stop]).collect());
+    assert!((width / height - original_width / original_height).abs() < 1e-9);
+    assert!(height < width);
+
+    // y points up
+    for ((start, stop), (ndc_start, ndc_stop)) in htree.into_iter().zip(htree.ndc_iter()) {
+        assert!((stop.1 - start.1) * (ndc_stop.1 - ndc_start.1) <= 0.0);
+    }
+}
+
+#[test]
+fn test_to_ascii_dimensions() {
+    let htree: HTree<f64> = HTree::new(3);
+    let ascii = htree.to_ascii(40, 12);
+    let lines: Vec<_> = ascii.lines().collect();
+    assert_eq!(lines.len(), 12);
+    for line in &lines {
+        assert_eq!(line.chars().count(), 40);
+    }
+    assert!(ascii.chars().any(|c| "│─┼├┤┬┴".contains(c)));
+}
+
+#[test]
+fn test_to_ascii_zero_sized_grid() {
+    let htree: HTree<f64> = HTree::new(3);
+    assert_eq!(htree.to_ascii(0, 0), "");
+    assert_eq!(htree.to_ascii(40, 0), "");
+    assert_eq!(htree.to_ascii(0, 3), "\n\n");
+    assert_eq!(htree.to_ascii(0, 3).split('\n').count(), 3);
+}
+
+#[test]
+fn test_iter_with_orientation() {
+    use htree::Orientation;
+    let htree: HTree<f64> = HTree::new(8);
+    for (orientation, (start, stop)) in htree.iter_with_orientation() {
+        let (dx, dy) = ((stop.0 - start.0).abs(), (stop.1 - start.1).abs());
+        match orientation {
+            Orientation::Horizontal => assert!(dx > 0.0 && dy == 0.0),
+            Orientation::Vertical => assert!(dy > 0.0 && dx == 0.0),
+        }
+    }
+}
+
+#[test]
+fn test_sampled() {
+    let htree: HTree<f64> = HTree::new(10);
+    let all: Vec<_> = htree.into_iter().collect();
+    assert_eq!(htree.sampled(1).collect::<Vec<_>>(), all);
+
+    let quarter: Vec<_> = htree.sampled(4).collect();
+    assert_eq!(quarter.len(), all.len().div_ceil(4));
+    for (sample, line) in quarter.iter().zip(all.iter().step_by(4)) {
+        assert_eq!(sample, line);
+    }
+}
+
+#[test]
+fn test_segment_at_beyond_the_last_line() {
+    for htree in [HTree::<f64>::new(10), HTree::with_branching(6, 3)] {
+        assert_eq!(htree.segment_at(htree.len()), None);
+        assert_eq!(htree.segment_at(usize::MAX), None);
+        assert_eq!(htree.segment_at(usize::MAX - 1), None);
+    }
+}
+
+#[test]
+fn test_index_range_iter_windows() {
+    let htree: HTree<f64> = HTree::new(7);
+    let all: Vec<_> = htree.into_iter().collect();
+    for k in [0, 1, 100, all.len()] {
+        let mut windows: Vec<_> = htree.index_range_iter(0..k).collect();
+        windows.extend(htree.index_range_iter(k..all.len()));
+        assert_eq!(windows, all);
+    }
+}
+
+#[test]
+fn test_convex_hull_contains_endpoints() {
+    for order in [0, 1, 4, 7] {
+        let htree: HTree<f64> = HTree::new(order);
+        let hull = htree.convex_hull();
+        assert!(hull.len() >= 2);
+        for point in htree.into_iter().flat_map(|(start, stop)| [start, stop]) {
+            for (i, &a) in hull.iter().enumerate() {
+                let b = hull[(i + 1) % hull.len()];
+                assert!(cross((b.0 - a.0, b.1 - a.1), (point.0 - a.0, point.1 - a.1)) >= -1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_quad_symmetric_mirrors() {
+    let htree: HTree<f64> = HTree::new(5);
+    let lines: Vec<_> = htree.quad_symmetric().collect();
+    assert_eq!(lines.len(), 4 * htree.len());
+
+    let height = std::f64::consts::FRAC_1_SQRT_2;
+    let key = |((a, b), (c, d)): ((f64, f64), (f64, f64))| {
+        let round = |v: f64| (v * 1e9).round() as i64;
+        let (start, stop) = ((round(a), round(b)), (round(c), round(d)));
+        (start.min(stop), start.max(stop))
+    };
+    let mut expected: Vec<_> = lines.iter().copied().map(key).collect();
+    expected.sort();
+    for mirror in [
+        |(x, y): (f64, f64), _: f64| (1.0 - x, y),
+        |(x, y): (f64, f64), height: f64| (x, height - y),
+    ] {
+        let mut mirrored: Vec<_> = lines
+            .iter()
+            .map(|&(start, stop)| key((mirror(start, height), mirror(stop, height))))
+            .collect();
+        mirrored.sort();
+        assert_eq!(mirrored, expected);
+    }
+    for ((x, y), _) in &lines[..htree.len()] {
+        assert!(*x <= 0.5 && *y <= height / 2.0);
+    }
+}
+
+#[test]
+fn test_dashed() {
+    let htree: HTree<f64> = HTree::new(6);
+    let (dash, gap) = (0.03, 0.01);
+    let mut total = 0.0;
+    for ((a, b), (c, d)) in htree.dashed(dash, gap) {
+        let length = (c - a).hypot(d - b);
+        assert!(length <= dash + 1e-12);
+        total += length;
+    }
+    assert!(total < htree.total_length());
+    assert!(total > htree.total_length() / 2.0);
+
+    // lines shorter than a dash stay whole
+    let whole: Vec<_> = htree.dashed(1.0, 0.5).collect();
+    assert_eq!(whole, htree.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_midpoints() {
+    let htree: HTree<f64> = HTree::new(7);
+    assert_eq!(htree.midpoints().count(), htree.len());
+    for ((x, y), (start, stop)) in htree.midpoints().zip(htree) {
+        assert_eq!((x, y), ((start.0 + stop.0) / 2.0, (start.1 + stop.1) / 2.0));
+    }
+}
+
+#[test]
+fn test_lod_iter() {
+    let (order, coarse_level) = (9, 3);
+    let htree: HTree<f64> = HTree::new(order);
+    let level_of = |line: ((f64, f64), (f64, f64))| {
+        (0..=order)
+            .find(|&level| htree.segments_between(level, level).any(|other| other == line))
+            .unwrap()
+    };
+    let focus = ((0.2, 0.1), (0.45, 0.4));
+    let inside = |(x, y): (f64, f64)| (0.2..=0.45).contains(&x) && (0.1..=0.4).contains(&y);
+
+    let lines: Vec<_> = htree.lod_iter(focus, coarse_level).collect();
+    let mut deepest_inside = 0;
+    for &(start, stop) in &lines {
+        let level = level_of((start, stop));
+        if inside(((start.0 + stop.0) / 2.0, (start.1 + stop.1) / 2.0)) {
+            deepest_inside = deepest_inside.max(level);
+        } else {
+            assert!(level <= coarse_level);
+        }
+    }
+    assert_eq!(deepest_inside, order);
+    let coarse = lines.iter().filter(|&&line| level_of(line) <= coarse_level).count();
+    assert_eq!(coarse, (1 << (coarse_level + 1)) - 1);
+}
+
+#[test]
+fn test_outline_contains_bounds() {
+    let bounds = |points: &[(f64, f64)]| {
+        points.iter().fold(
+            ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY)),
+            |(min, max), &(x, y)| ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y))),
+        )
+    };
+    for order in [0, 1, 4, 7] {
+        let htree: HTree<f64> = HTree::new(order);
+        let width = 0.01;
+        let outline = htree.outline(width);
+        let endpoints: Vec<_> = htree.into_iter().flat_map(|(start, stop)| [start, stop]).collect();
+        let (outline_min, outline_max) = bounds(&outline);
+        let (min, max) = bounds(&endpoints);
+        assert!(outline_min.0 <= min.0 - width / 2.0 + 1e-12 && outline_min.1 <= min.1 - width / 2.0 + 1e-12);
+        assert!(outline_max.0 >= max.0 + width / 2.0 - 1e-12 && outline_max.1 >= max.1 + width / 2.0 - 1e-12);
+
+        // counter clockwise with axis aligned edges
+        let area: f64 = (0..outline.len())
+            .map(|i| cross(outline[i], outline[(i + 1) % outline.len()]))
+            .sum();
+        assert!(area > 0.0);
+        for i in 0..outline.len() {
+            let (a, b) = (outline[i], outline[(i + 1) % outline.len()]);
+            assert!(a.0 == b.0 || a.1 == b.1);
+        }
+    }
+}
+
+#[test]
+fn test_rasterize_1bpp() {
+    let count = |order: usize| {
+        let mut framebuffer = [0u8; 16 * 11 / 8];
+        HTree::<f64>::new(order).rasterize_1bpp(&mut framebuffer, 16, 11);
+        framebuffer.iter().map(|byte| byte.count_ones()).sum::<u32>()
+    };
+    // the crossbar covers columns 4 to 11 of row 5, the verticals rows 2 to 8
+    assert_eq!(count(0), 8);
+    assert_eq!(count(1), 8 + 2 * 7 - 2);
+}
+
+#[test]
+#[should_panic(expected = "too small")]
+fn test_rasterize_1bpp_checks_buffer() {
+    HTree::<f64>::new(2).rasterize_1bpp(&mut [0u8; 7], 8, 8);
+}
+
+#[test]
+fn test_level_cache() {
+    for emission_order in [htree::EmissionOrder::AsBuilt, htree::EmissionOrder::DepthFirst] {
+        let htree: HTree<f64> = HTree::new(8).with_emission_order(emission_order);
+        let cache = htree.level_cache();
+        for level in 0..=9 {
+            assert_eq!(cache.level(level), htree.level(level).collect::<Vec<_>>());
+        }
+        assert!(cache.level(9).is_empty());
+    }
+}
+
+#[test]
+fn test_segment_at_cell() {
+    let order = 6;
+    let htree: HTree<f64> = HTree::new(order);
+    let aspect = std::f64::consts::FRAC_1_SQRT_2;
+    for level in 0..=order {
+        let (horizontal, vertical) = htree.debug_level_grid(level);
+        let (rows, columns) = if level % 2 == 1 { (vertical / 2, horizontal) } else { (vertical, horizontal / 2) };
+        for line in htree.level(level) {
+            let midpoint = ((line.0 .0 + line.1 .0) / 2.0, (line.0 .1 + line.1 .1) / 2.0);
+            let col = (midpoint.0 * columns as f64).floor() as u32;
+            let row = (midpoint.1 / aspect * rows as f64).floor() as u32;
+            assert_eq!(htree.segment_at_cell(level, col, row), Some(line));
+        }
+        assert_eq!(htree.segment_at_cell(level, columns, 0), None);
+        assert_eq!(htree.segment_at_cell(level, 0, rows), None);
+    }
+    assert_eq!(htree.segment_at_cell(order + 1, 0, 0), None);
+}
+
+#[test]
+fn test_segment_neighbors_are_symmetric() {
+    use htree::EmissionOrder;
+    for emission_order in [EmissionOrder::AsBuilt, EmissionOrder::RowMajor, EmissionOrder::DepthFirst] {
+        let htree: HTree<f64> = HTree::new(4).with_emission_order(emission_order);
+        for index in 0..htree.len() {
+            let [up, down, left, right] = htree.segment_neighbors(index);
+            let (start, _) = htree.segment_at(index).unwrap();
+            let directions = [(up, 1, (0, -1)), (down, 0, (0, 1)), (left, 3, (-1, 0)), (right, 2, (1, 0))];
+            for (neighbour, opposite, offset) in directions {
+                if let Some(neighbour) = neighbour {
+                    assert_eq!(htree.segment_neighbors(neighbour)[opposite], Some(index));
+                    // the neighbour lies in that direction
+                    let (other, _) = htree.segment_at(neighbour).unwrap();
+                    let step = ((other.0 - start.0).signum() as i32, (other.1 - start.1).signum() as i32);
+                    let step = (if offset.0 == 0 { 0 } else { step.0 }, if offset.1 == 0 { 0 } else { step.1 });
+                    assert_eq!(step, offset);
+                }
+            }
+        }
+    }
+    assert_eq!(HTree::<f64>::new(2).segment_neighbors(7), [None; 4]);
+}
+
+#[test]
+fn test_segment_fn_matches_segment_at() {
+    let htree: HTree<f64> = HTree::new(9);
+    let segment = htree.segment_fn();
+    for index in 0..htree.len() {
+        assert_eq!(Some(segment(index)), htree.segment_at(index));
+    }
+}
+
+#[test]
+#[should_panic(expected = "beyond")]
+fn test_segment_fn_panics_beyond_order() {
+    let htree: HTree<f64> = HTree::new(3);
+    htree.segment_fn()(15);
+}
+
+#[test]
+fn test_with_size_scales_axes_independently() {
+    let htree: HTree<f64> = HTree::with_size(5, 800.0, 300.0);
+    let unit: HTree<f64> = HTree::new(5).with_shape(Shape::Square);
+    for ((start, stop), (unit_start, unit_stop)) in htree.into_iter().zip(unit) {
+        for (x, y) in [start, stop] {
+            assert!((0.0..=800.0).contains(&x) && (0.0..=300.0).contains(&y), "({x}, {y}) is out of bounds");
+        }
+        assert!((start.0 - unit_start.0 * 800.0).abs() < 1e-9 && (start.1 - unit_start.1 * 300.0).abs() < 1e-9);
+        assert!((stop.0 - unit_stop.0 * 800.0).abs() < 1e-9 && (stop.1 - unit_stop.1 * 300.0).abs() < 1e-9);
+    }
+    let visible = htree.segments_in_rect((0.0, 0.0), (800.0, 300.0)).count();
+    assert_eq!(visible, htree.len());
+}
+
+#[test]
+fn test_density_grid_counts_every_segment() {
+    for order in 0..6 {
+        let htree: HTree<f64> = HTree::new(order);
+        let density = htree.density_grid(20, 14);
+        assert_eq!(density.len(), 20 * 14);
+        assert!(density.iter().sum::<u32>() as usize >= htree.len());
+        // pixels drawn by the framebuffer are exactly the pixels with a positive count
+        let mut framebuffer = vec![0u8; (20 * 14usize).div_ceil(8)];
+        htree.rasterize_1bpp(&mut framebuffer, 20, 14);
+        for (index, &count) in density.iter().enumerate() {
+            assert_eq!(count > 0, framebuffer[index / 8] & (0x80 >> (index % 8)) != 0);
+        }
+    }
+    assert!(HTree::<f64>::new(3).density_grid(0, 5).is_empty());
+}
+
+#[test]
+fn test_until_length_stops_above_min_len() {
+    for (min_len, aspect) in [(0.1, 0.5f64.sqrt()), (0.003, 0.5f64.sqrt()), (0.02, 1.0), (0.05, 0.3)] {
+        let htree: HTree<f64> = HTree::until_length(min_len, aspect);
+        let shortest = |htree: HTree<f64>| {
+            htree.into_iter().map(|(start, stop)| (stop.0 - start.0).hypot(stop.1 - start.1)).fold(f64::MAX, f64::min)
+        };
+        assert!(shortest(htree) >= min_len);
+        let deeper: HTree<f64> = HTree::with_size(htree.order() + 1, 1.0, aspect);
+        assert!(shortest(deeper) < min_len);
+        let (_, stop) = htree.into_iter().next().unwrap();
+        assert_eq!(stop, (0.75, aspect / 2.0));
+    }
+}
+
+#[test]
+fn test_fit_to_print_stays_on_paper() {
+    let htree: HTree<f32> = HTree::new(8);
+    let (width, height) = (210.0 / 25.4 * 300.0, 297.0 / 25.4 * 300.0);
+    let (mut max_x, mut max_y) = (0.0f64, 0.0f64);
+    for (start, stop) in htree.fit_to_print(210.0, 297.0, 300.0) {
+        for (x, y) in [start, stop] {
+            assert!(x >= 0.0 && y >= 0.0);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+    }
+    assert!(max_x <= width && max_y <= height);
+    // the width is the limiting side of a portrait page
+    assert!((max_x - width * (1.0 - 1.0 / 64.0)).abs() < 1e-2);
+}
+
+#[test]
+fn test_index_of_cell_inverts_iteration() {
+    for htree in [HTree::<f64>::new(7), HTree::new(6).with_emission_order(EmissionOrder::DepthFirst)] {
+        let height = 0.5f64.sqrt();
+        for (index, (start, stop)) in htree.into_iter().enumerate() {
+            let level = (0..=htree.order())
+                .find(|&level| htree.level(level).any(|line| line == (start, stop)))
+                .unwrap();
+            let (columns, rows) = htree.debug_level_grid(level);
+            for (x, y) in [start, stop] {
+                let (column, row) = ((x * columns as f64) as u32, (y / height * rows as f64) as u32);
+                let rect_index = if level % 2 == 1 { column * rows + row } else { row * columns + column };
+                assert_eq!(htree.index_of_cell(level, rect_index), index);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_pixel_snap_hits_pixel_centers() {
+    let htree: HTree<f64> = HTree::new(8);
+    let (width, height) = (300, 213);
+    let snapped: Vec<_> = htree.pixel_snap(width, height).collect();
+    assert_eq!(snapped.len(), htree.len());
+    for &(start, stop) in &snapped {
+        for (x, y) in [start, stop] {
+            assert_eq!((x.fract(), y.fract()), (0.5, 0.5));
+            assert!(x > 0.0 && x < width as f64 && y > 0.0 && y < height as f64);
+        }
+    }
+    // children stay attached to the ends of their parents
+    for (parent, index, _) in htree.iter_with_parent() {
+        if let Some(parent) = parent {
+            let (start, stop) = snapped[index];
+            let (parent_start, parent_stop) = snapped[parent];
+            let attached = |end: (f64, f64)| {
+                let between = |value: f64, a: f64, b: f64| value >= a.min(b) && value <= a.max(b);
+                between(end.0, start.0, stop.0) && between(end.1, start.1, stop.1)
+            };
+            assert!(attached(parent_start) || attached(parent_stop));
+        }
+    }
+}
+
+#[test]
+fn test_fill_ratio_grows_with_line_width() {
+    let htree: HTree<f64> = HTree::new(6);
+    let mut previous = 0.0;
+    for line_width in [0.0001, 0.001, 0.005, 0.01, 0.02, 0.05] {
+        let ratio = htree.fill_ratio(line_width);
+        assert!(ratio > previous && ratio <= 1.0, "ratio {ratio} for width {line_width}");
+        // overlaps are counted once, so the ratio stays below the sum of all strokes
+        let strokes: f64 = htree
+            .into_iter()
+            .map(|(start, stop)| ((stop.0 - start.0).hypot(stop.1 - start.1) + line_width) * line_width)
+            .sum();
+        assert!(ratio * 0.5f64.sqrt() < strokes);
+        previous = ratio;
+    }
+    assert_eq!(htree.fill_ratio(10.0), 1.0);
+}
+
+#[test]
+fn test_with_camera() {
+    let htree: HTree<f64> = HTree::new(5);
+    assert!(htree.with_camera((0.0, 0.0), 1.0).eq(htree));
+    let zoomed: Vec<_> = htree.with_camera((0.5, 0.25), 2.0).collect();
+    for (&(start, stop), (original_start, original_stop)) in zoomed.iter().zip(htree) {
+        assert_eq!(start, ((original_start.0 - 0.5) * 2.0, (original_start.1 - 0.25) * 2.0));
+        assert_eq!(stop, ((original_stop.0 - 0.5) * 2.0, (original_stop.1 - 0.25) * 2.0));
+    }
+    // the middle of the first line is at (0.5, 1/sqrt(8))
+    let (start, _) = zoomed[0];
+    assert_eq!(start.0, -0.5);
+    assert!((start.1 - (2.0 * 0.125f64.sqrt() - 0.5)).abs() < 1e-12);
+}
+
+#[test]
+fn test_crossed_is_symmetric_under_quarter_turns() {
+    let key = |value: f64| (value * 1e9).round() as i64;
+    let normalized = |lines: Vec<((f64, f64), (f64, f64))>| {
+        let mut keys: Vec<_> = lines
+            .into_iter()
+            .map(|(a, b)| {
+                let (a, b) = ((key(a.0), key(a.1)), (key(b.0), key(b.1)));
+                (a.min(b), a.max(b))
+            })
+            .collect();
+        keys.sort();
+        keys
+    };
+    for htree in [HTree::<f64>::new(6), HTree::new(5).with_shape(Shape::Square)] {
+        let crossed: Vec<_> = htree.crossed().collect();
+        assert_eq!(crossed.len(), 2 * htree.len());
+        let center = (0.5, if htree.shape() == Shape::Square { 0.5 } else { 0.5f64.sqrt() / 2.0 });
+        let rotate = |(x, y): (f64, f64)| (center.0 - (y - center.1), center.1 + (x - center.0));
+        let rotated: Vec<_> = crossed.iter().map(|&(start, stop)| (rotate(start), rotate(stop))).collect();
+        assert_eq!(normalized(rotated), normalized(crossed));
+    }
+}
+
+#[test]
+fn test_distance_to_matches_brute_force() {
+    let htree: HTree<f64> = HTree::new(7);
+    let brute_force = |p: (f64, f64)| {
+        htree
+            .into_iter()
+            .map(|(start, stop)| {
+                let (dx, dy) = (stop.0 - start.0, stop.1 - start.1);
+                let t = (((p.0 - start.0) * dx + (p.1 - start.1) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+                (p.0 - start.0 - t * dx).hypot(p.1 - start.1 - t * dy)
+            })
+            .fold(f64::MAX, f64::min)
+    };
+    for (start, stop) in htree {
+        let on_line = (start.0 * 0.3 + stop.0 * 0.7, start.1 * 0.3 + stop.1 * 0.7);
+        assert!(htree.distance_to(on_line) < 1e-12);
+    }
+    let mut state = 12345u64;
+    let mut random = || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+    for _ in 0..500 {
+        let p = (random() * 1.4 - 0.2, random() * 1.1 - 0.2);
+        let distance = htree.distance_to(p);
+        assert!((distance - brute_force(p)).abs() < 1e-12, "{p:?}");
+    }
+    assert!((htree.distance_to((3.0, 0.0)) - brute_force((3.0, 0.0))).abs() < 1e-12);
+    assert!(htree.distance_to((0.0, 0.0)) > 0.0);
+}
+
+#[test]
+fn test_canvas_size_keeps_the_aspect() {
+    let htree: HTree<f64> = HTree::new(8);
+    let (width, height) = htree.canvas_size(1000);
+    assert_eq!(width, 1000);
+    assert!((height as f64 / width as f64 - 0.5f64.sqrt()).abs() < 1e-3);
+    assert_eq!(HTree::<f64>::with_size(3, 300.0, 800.0).canvas_size(1600), (600, 1600));
+    assert_eq!(HTree::<f64>::with_size(3, 300.0, 800.0).canvas_size(1), (1, 1));
+}
+
+#[test]
+fn test_iter_with_normal_is_unit_and_orthogonal() {
+    let htree: HTree<f64> = HTree::with_branching(5, 3);
+    assert_eq!(htree.iter_with_normal().count(), htree.len());
+    for ((start, stop), normal) in htree.iter_with_normal() {
+        let direction = (stop.0 - start.0, stop.1 - start.1);
+        assert!((normal.0.hypot(normal.1) - 1.0).abs() < 1e-12);
+        assert!((direction.0 * normal.0 + direction.1 * normal.1).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_useful_order_for_resolution_stops_at_one_pixel() {
+    let shortest = |order: usize| {
+        let htree: HTree<f64> = HTree::new(order);
+        htree.deepest_level().map(|(start, stop)| (stop.0 - start.0).hypot(stop.1 - start.1)).fold(f64::MAX, f64::min)
+    };
+    for (width, height) in [(1000, 1000), (1920, 1080), (300, 2000), (64, 64)] {
+        let order = HTree::<f64>::useful_order_for_resolution(width, height);
+        let scale = (width as f64).min(height as f64 / 0.5f64.sqrt());
+        assert!(shortest(order) * scale >= 1.0);
+        assert!(shortest(order + 1) * scale < 1.0);
+    }
+}
+
+#[test]
+fn test_partition_by_orientation() {
+    let htree: HTree<f64> = HTree::with_branching(5, 3);
+    let (horizontals, verticals) = htree.partition_by_orientation();
+    assert!(horizontals.iter().all(|(start, stop)| start.1 == stop.1 && start.0 != stop.0));
+    assert!(verticals.iter().all(|(start, stop)| start.0 == stop.0 && start.1 != stop.1));
+
+    let mut partitioned: Vec<_> = horizontals.into_iter().chain(verticals).collect();
+    let mut all: Vec<_> = htree.into_iter().collect();
+    let key = |a: &((f64, f64), (f64, f64)), b: &((f64, f64), (f64, f64))| {
+        [a.0 .0, a.0 .1, a.1 .0, a.1 .1].partial_cmp(&[b.0 .0, b.0 .1, b.1 .0, b.1 .1]).unwrap()
+    };
+    partitioned.sort_by(key);
+    all.sort_by(key);
+    assert_eq!(partitioned, all);
+}
+
+#[test]
+fn test_hilbert_ordered_is_a_local_permutation() {
+    let htree: HTree<f64> = HTree::new(9);
+    let ordered = htree.hilbert_ordered(64);
+    let key = |a: &((f64, f64), (f64, f64)), b: &((f64, f64), (f64, f64))| {
+        [a.0 .0, a.0 .1, a.1 .0, a.1 .1].partial_cmp(&[b.0 .0, b.0 .1, b.1 .0, b.1 .1]).unwrap()
+    };
+    let mut sorted = ordered.clone();
+    sorted.sort_by(key);
+    let mut all: Vec<_> = htree.into_iter().collect();
+    all.sort_by(key);
+    assert_eq!(sorted, all);
+
+    // consecutive midpoints are much closer than in the order level by level
+    let mean_step = |lines: &[Line<f64>]| {
+        let midpoints: Vec<(f64, f64)> =
+            lines.iter().map(|(start, stop)| ((start.0 + stop.0) / 2.0, (start.1 + stop.1) / 2.0)).collect();
+        let total: f64 = midpoints.windows(2).map(|pair| (pair[1].0 - pair[0].0).hypot(pair[1].1 - pair[0].1)).sum();
+        total / (midpoints.len() - 1) as f64
+    };
+    let level_by_level: Vec<_> = htree.into_iter().collect();
+    assert!(mean_step(&ordered) < mean_step(&level_by_level) / 2.0);
+}
+
+#[test]
+fn test_canonical_segments_ignore_order_and_direction() {
+    let htree: HTree<f64> = HTree::with_branching(5, 3);
+    let canonical = htree.canonical_segments(6);
+    assert_eq!(canonical.len(), htree.len());
+    assert!(canonical.iter().all(|(start, stop)| start < stop));
+    assert!(canonical.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    let quantize = |value: f64| (value * 1e6).round() as i64;
+    let mut reversed: Vec<_> = htree
+        .reversed_segments()
+        .map(|(start, stop)| {
+            let (start, stop) = ((quantize(start.0), quantize(start.1)), (quantize(stop.0), quantize(stop.1)));
+            (start.min(stop), start.max(stop))
+        })
+        .collect();
+    reversed.sort();
+    assert_eq!(canonical, reversed);
+
+    for order in [EmissionOrder::RowMajor, EmissionOrder::ColMajor, EmissionOrder::DepthFirst] {
+        assert_eq!(htree.with_emission_order(order).canonical_segments(6), canonical);
+    }
+}
+
+#[test]
+fn test_clipped_to_circle_stays_within_the_disc() {
+    let htree: HTree<f64> = HTree::new(8);
+    let (center, radius) = ((0.5, 0.3), 0.25);
+    let clipped: Vec<_> = htree.clipped_to_circle(center, radius).collect();
+    assert!(!clipped.is_empty() && clipped.len() < htree.len());
+    for (start, stop) in &clipped {
+        for point in [start, stop] {
+            assert!((point.0 - center.0).hypot(point.1 - center.1) <= radius + 1e-12);
+        }
+    }
+    // lines within the disc are kept unchanged
+    let inside = htree.into_iter().filter(|&(start, stop)| {
+        [start, stop].iter().all(|point| (point.0 - center.0).hypot(point.1 - center.1) < radius)
+    });
+    for line in inside {
+        assert!(clipped.contains(&line));
+    }
+    // a large disc keeps every line
+    assert_eq!(htree.clipped_to_circle(center, 2.0).count(), htree.len());
+}
+
+#[test]
+fn test_parametric_segments_reach_the_endpoints() {
+    let htree: HTree<f64> = HTree::with_branching(4, 3);
+    assert_eq!(htree.parametric_segments().count(), htree.len());
+    for (line, (start, stop)) in htree.parametric_segments().zip(&htree) {
+        assert_eq!(line.eval(0.0), start);
+        let end = line.eval(1.0);
+        assert!((end.0 - stop.0).abs() < 1e-12 && (end.1 - stop.1).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_stroked_area_counts_junctions_once() {
+    let htree: HTree<f64> = HTree::new(4);
+    let width = 0.01;
+    let naive = htree.total_length() * width;
+    let area = htree.stroked_area(width);
+    assert!(area < naive);
+    // every line but the first overlaps half a square of the stroke of its parent
+    let overlaps = (htree.len() - 1) as f64 * width * width / 2.0;
+    assert!((area - (naive - overlaps)).abs() < 1e-12);
+}
+
+#[test]
+fn test_with_length_variation() {
+    let variation = 0.25;
+    let varied: HTree<f64> = HTree::with_length_variation(6, variation, 3);
+    let lines: Vec<_> = varied.into_iter().collect();
+    assert_eq!(lines, HTree::with_length_variation(6, variation, 3).into_iter().collect::<Vec<_>>());
+    assert_ne!(lines, HTree::with_length_variation(6, variation, 4).into_iter().collect::<Vec<_>>());
+
+    let length = |(start, stop): ((f64, f64), (f64, f64))| (stop.0 - start.0).hypot(stop.1 - start.1);
+    let mut ratios: Vec<f64> = lines.iter().zip(&HTree::<f64>::new(6)).map(|(&a, b)| length(a) / length(b)).collect();
+    assert!(ratios.iter().all(|ratio| (1.0 - variation - 1e-12..=1.0 + variation + 1e-12).contains(ratio)));
+    ratios.dedup();
+    assert!(ratios.len() > 1);
+
+    assert!(varied.is_connected());
+    assert_eq!(varied.collect_all(), lines);
+    let (min, max) = ((0.1, 0.2), (0.4, 0.5));
+    let inside: Vec<_> = lines
+        .iter()
+        .copied()
+        .filter(|(start, stop)| {
+            start.0.min(stop.0) <= max.0
+                && start.0.max(stop.0) >= min.0
+                && start.1.min(stop.1) <= max.1
+                && start.1.max(stop.1) >= min.1
+        })
+        .collect();
+    assert_eq!(varied.segments_in_rect(min, max).count(), inside.len());
+}
+
+#[test]
+fn test_balanced_ranges_cover_all_lines() {
+    let htree: HTree<f64> = HTree::with_branching(4, 3);
+    for n in [1, 2, 7, 64, htree.len(), htree.len() + 5] {
+        let ranges = htree.balanced_ranges(n);
+        assert_eq!(ranges.len(), n);
+        assert_eq!(ranges[0].start, 0);
+        assert_eq!(ranges[n - 1].end, htree.len());
+        assert!(ranges.windows(2).all(|pair| pair[0].end == pair[1].start));
+        let lengths: Vec<usize> = ranges.iter().map(|range| range.len()).collect();
+        assert!(lengths.iter().max().unwrap() - lengths.iter().min().unwrap() <= 1);
+
+        let lines: Vec<_> = ranges.into_iter().flat_map(|range| htree.index_range_iter(range)).collect();
+        assert_eq!(lines, htree.into_iter().collect::<Vec<_>>());
+    }
+    assert!(htree.balanced_ranges(0).is_empty());
+}
+
+#[test]
+fn test_line_mesh_covers_every_stroke() {
+    let htree: HTree<f64> = HTree::with_branching(4, 3);
+    let width = 0.004;
+    let (vertices, indices) = htree.to_line_mesh(width);
+    assert_eq!(vertices.len(), 4 * htree.len());
+    assert_eq!(indices.len(), 6 * htree.len());
+    assert!(indices.iter().all(|&index| (index as usize) < vertices.len()));
+
+    // the two triangles of every line add up to the area of its stroke
+    let area = |a: (f64, f64), b: (f64, f64), c: (f64, f64)| {
+        ((b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)).abs() / 2.0
+    };
+    for ((start, stop), triangles) in htree.into_iter().zip(indices.chunks(6)) {
+        let corners: Vec<_> = triangles.iter().map(|&index| vertices[index as usize]).collect();
+        let covered = area(corners[0], corners[1], corners[2]) + area(corners[3], corners[4], corners[5]);
+        assert!((covered - (stop.0 - start.0).hypot(stop.1 - start.1) * width).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_iter_with_arclength_ends_at_the_total_length() {
+    for htree in [HTree::<f64>::new(10), HTree::with_branching(5, 3).with_shape(Shape::Square)] {
+        let lengths: Vec<f64> = htree.iter_with_arclength().map(|(length, _)| length).collect();
+        assert_eq!(lengths.len(), htree.len());
+        assert!(lengths.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!((lengths[lengths.len() - 1] - htree.total_length()).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_spatial_index_matches_nearest_segment() {
+    let htree: HTree<f64> = HTree::new(8);
+    let lines: Vec<_> = htree.into_iter().collect();
+    let mut state = 777u64;
+    let mut random = || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+    for cells in [1, 7, 64] {
+        let index = htree.build_spatial_index(cells);
+        for _ in 0..500 {
+            let p = (random() * 1.4 - 0.2, random() * 1.1 - 0.2);
+            let (nearest, distance) = index.nearest(p);
+            assert_eq!(lines[nearest], htree.nearest_segment(p), "{p:?}");
+            assert_eq!(distance, htree.distance_to(p), "{p:?}");
+        }
+        assert_eq!(index.nearest((5.0, -3.0)).1, htree.distance_to((5.0, -3.0)));
+    }
+}
+
+#[test]
+fn test_fill_rings_are_closed_and_bound_the_strokes() {
+    let htree: HTree<f64> = HTree::new(4);
+    let single = htree.fill_rings(0.01);
+    assert_eq!(single.len(), 1);
+    assert_eq!(single[0][..single[0].len() - 1], htree.outline(0.01)[..]);
+
+    let square = HTree::new(4).with_shape(Shape::Square);
+    for (htree, width) in [(htree, 0.01), (HTree::new(3), 0.1), (square, 0.1)] {
+        let rings = htree.fill_rings(width);
+        // signed areas with the y axis pointing up, negative around holes, add up to the area of the strokes
+        let areas: Vec<f64> = rings
+            .iter()
+            .map(|ring| {
+                assert!(ring.len() >= 5);
+                assert_eq!(ring[0], ring[ring.len() - 1]);
+                ring.windows(2).map(|pair| pair[0].0 * pair[1].1 - pair[1].0 * pair[0].1).sum::<f64>() / 2.0
+            })
+            .collect();
+        let (box_width, box_height) = (1.0, if htree.shape() == Shape::Square { 1.0 } else { 0.5f64.sqrt() });
+        let area: f64 = areas.iter().sum();
+        assert!((area - htree.fill_ratio(width) * box_width * box_height).abs() < 1e-12, "{width}");
+        assert!(areas[0] > 0.0);
+        assert_eq!(areas.iter().any(|&area| area < 0.0), width == 0.1);
+    }
+}
+
+#[test]
+fn test_symmetries() {
+    let mirrors = Symmetries {
+        vertical_mirror: true,
+        horizontal_mirror: true,
+        half_turn: true,
+        ..Symmetries::default()
+    };
+    assert_eq!(HTree::<f64>::new(5).symmetries(), mirrors);
+    assert_eq!(HTree::<f64>::new(0).symmetries(), mirrors);
+    assert_eq!(HTree::<f64>::with_branching(4, 3).symmetries(), mirrors);
+    // the first line is horizontal, so even the square HTree has no quarter turn symmetry
+    assert_eq!(HTree::<f64>::new(6).with_shape(Shape::Square).symmetries(), mirrors);
+    assert_eq!(HTree::<f64>::with_length_variation(5, 0.3, 1).symmetries(), Symmetries::default());
+}
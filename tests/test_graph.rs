@@ -0,0 +1,241 @@
+use htree::HTree;
+
+fn length(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+#[test]
+fn test_polylines_cover_all_lines() {
+    let htree: HTree<f64> = HTree::new(6);
+    let lines: f64 = htree.into_iter().map(|(start, stop)| length(start, stop)).sum();
+    let polylines: f64 = htree
+        .polylines()
+        .iter()
+        .flat_map(|polyline| polyline.windows(2).map(|pair| length(pair[0], pair[1])))
+        .sum();
+    assert!((lines - polylines).abs() < 1e-9);
+}
+
+#[test]
+fn test_leaf_points_are_tips() {
+    for order in 0..7 {
+        let htree: HTree<f64> = HTree::new(order);
+        let leaves = htree.leaf_points();
+        assert_eq!(leaves.len(), 1 << (order + 1));
+        for leaf in leaves {
+            let epsilon = 1e-9;
+            let touching = htree
+                .segments_in_rect((leaf.0 - epsilon, leaf.1 - epsilon), (leaf.0 + epsilon, leaf.1 + epsilon))
+                .count();
+            assert_eq!(touching, 1);
+        }
+    }
+}
+
+#[test]
+fn test_unpruned_tree_is_one_component() {
+    let htree: HTree<f64> = HTree::new(5);
+    let components = htree.connected_components();
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0], htree.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_iter_with_parent() {
+    use htree::EmissionOrder;
+    for htree in [
+        HTree::<f64>::new(7),
+        HTree::<f64>::new(7).with_emission_order(EmissionOrder::RowMajor),
+        HTree::<f64>::new(7).with_emission_order(EmissionOrder::ColMajor),
+        HTree::<f64>::with_branching(4, 3),
+    ] {
+        let lines: Vec<_> = htree.into_iter().collect();
+        for (parent, index, (start, stop)) in htree.iter_with_parent() {
+            assert_eq!(lines[index], (start, stop));
+            let Some(parent) = parent else {
+                assert_eq!(index, 0);
+                continue;
+            };
+            assert!(parent < index);
+            // lines are centered on their parent
+            let (a, b) = lines[parent];
+            let middle = ((start.0 + stop.0) / 2.0, (start.1 + stop.1) / 2.0);
+            let cross = (b.0 - a.0) * (middle.1 - a.1) - (b.1 - a.1) * (middle.0 - a.0);
+            let inside = (middle.0 - a.0) * (middle.0 - b.0) + (middle.1 - a.1) * (middle.1 - b.1);
+            assert!(cross.abs() < 1e-12 && inside <= 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_is_connected() {
+    for order in 1..=8 {
+        assert!(HTree::<f64>::new(order).is_connected());
+        assert!(HTree::<f32>::new(order).is_connected());
+    }
+}
+
+#[test]
+fn test_vertex_valences() {
+    let htree: HTree<f64> = HTree::new(3);
+    let mut histogram = std::collections::BTreeMap::new();
+    for (_, valence) in htree.vertex_valences() {
+        *histogram.entry(valence).or_insert(0) += 1;
+    }
+    // 16 tips of the 8 deepest lines and 14 ends of the 7 inner lines
+    assert_eq!(histogram.into_iter().collect::<Vec<_>>(), vec![(1, 16), (3, 14)]);
+}
+
+#[test]
+fn test_no_self_intersections() {
+    for order in 1..=8 {
+        let htree: HTree<f64> = HTree::new(order);
+        assert_eq!(htree.self_intersections(), vec![], "order {order}");
+    }
+}
+
+#[test]
+fn test_to_nested() {
+    fn count(node: &htree::HNode<f64>) -> usize {
+        1 + node.children.iter().map(count).sum::<usize>()
+    }
+    fn depth(node: &htree::HNode<f64>) -> usize {
+        node.children.iter().map(|child| depth(child) + 1).max().unwrap_or(0)
+    }
+
+    for order in [0, 1, 6] {
+        let htree: HTree<f64> = HTree::new(order);
+        let root = htree.to_nested();
+        assert_eq!(count(&root), htree.len());
+        assert_eq!(depth(&root), order);
+        assert_eq!(Some(root.segment), htree.into_iter().next());
+    }
+}
+
+#[test]
+fn test_with_pads() {
+    let htree: HTree<f64> = HTree::new(5);
+    let pad_size = 0.004;
+    let (lines, pads) = htree.with_pads(pad_size);
+    assert!(lines.eq(htree));
+
+    let mut leaves = htree.leaf_points();
+    assert_eq!(pads.len(), leaves.len());
+    for &((x0, y0), (x1, y1)) in &pads {
+        assert!(((x1 - x0) - pad_size).abs() < 1e-12 && ((y1 - y0) - pad_size).abs() < 1e-12);
+        let center = ((x0 + x1) / 2.0, (y0 + y1) / 2.0);
+        let index = leaves
+            .iter()
+            .position(|&(x, y)| (x - center.0).hypot(y - center.1) < 1e-12)
+            .unwrap();
+        leaves.swap_remove(index);
+    }
+    assert!(leaves.is_empty());
+}
+
+#[test]
+fn test_dual_graph_is_a_grid() {
+    let htree: HTree<f64> = HTree::new(6);
+    for level in 0..=6 {
+        let (columns, rows) = htree.debug_level_grid(level);
+        let (columns, rows) = (columns as usize, rows as usize);
+        let (centers, links) = htree.dual_graph(level);
+        assert_eq!(centers.len(), columns * rows);
+        assert_eq!(links.len(), (columns - 1) * rows + columns * (rows - 1));
+        let mut degrees = vec![0; centers.len()];
+        for &(a, b) in &links {
+            assert!(a < b);
+            assert!(b == a + 1 && b % columns != 0 || b == a + columns);
+            degrees[a] += 1;
+            degrees[b] += 1;
+            // linked cells are one cell apart along one axis
+            let (dx, dy) = (centers[b].0 - centers[a].0, centers[b].1 - centers[a].1);
+            let expected = if b == a + 1 { (1.0 / columns as f64, 0.0) } else { (0.0, 0.5f64.sqrt() / rows as f64) };
+            assert!((dx - expected.0).abs() < 1e-12 && (dy - expected.1).abs() < 1e-12);
+        }
+        if columns > 1 && rows > 1 {
+            assert_eq!(degrees.iter().filter(|&&degree| degree == 2).count(), 4);
+            assert_eq!(*degrees.iter().max().unwrap(), 2 + (columns > 2) as usize + (rows > 2) as usize);
+        }
+    }
+    // endpoints of a level lie on the centers of its grid
+    let (centers, _) = htree.dual_graph(4);
+    for (start, stop) in htree.level(4) {
+        for point in [start, stop] {
+            assert!(centers.iter().any(|center| (center.0 - point.0).hypot(center.1 - point.1) < 1e-12));
+        }
+    }
+    assert_eq!(htree.dual_graph(7), (Vec::new(), Vec::new()));
+}
+
+#[test]
+fn test_complement_grid_only_shares_endpoints() {
+    for htree in [HTree::<f64>::new(5), HTree::with_branching(3, 3)] {
+        for level in 0..=htree.order() {
+            let (_, links) = htree.dual_graph(level);
+            let lines = htree.level(level).collect::<Vec<_>>();
+            let complement = htree.complement_grid(level).collect::<Vec<_>>();
+            assert_eq!(complement.len() + lines.len() * (htree.branches() as usize - 1), links.len());
+            for &(start, stop) in &complement {
+                for &(line_start, line_stop) in &lines {
+                    // a link overlaps a line if its middle lies on it
+                    let middle = ((start.0 + stop.0) / 2.0, (start.1 + stop.1) / 2.0);
+                    let between = |value: f64, a: f64, b: f64| value > a.min(b) - 1e-12 && value < a.max(b) + 1e-12;
+                    let on_line = between(middle.0, line_start.0, line_stop.0) && between(middle.1, line_start.1, line_stop.1);
+                    assert!(!on_line, "link {start:?} {stop:?} overlaps a line of level {level}");
+                }
+            }
+        }
+        assert_eq!(htree.complement_grid(htree.order() + 1).count(), 0);
+    }
+}
+
+#[test]
+fn test_to_dot_is_well_formed() {
+    let htree: HTree<f64> = HTree::new(6);
+    let dot = htree.to_dot();
+    assert!(dot.starts_with("graph {\n") && dot.ends_with("}\n"));
+    assert_eq!(dot.matches('{').count(), dot.matches('}').count());
+    let mut nodes = std::collections::HashSet::new();
+    let mut edges = 0;
+    for line in dot.lines().skip(1).filter(|line| *line != "}") {
+        let line = line.trim().strip_suffix(';').unwrap();
+        if let Some((a, b)) = line.split_once(" -- ") {
+            assert!(nodes.contains(a) && nodes.contains(b), "edge {line} references undeclared nodes");
+            edges += 1;
+        } else {
+            let (node, label) = line.split_once(' ').unwrap();
+            assert!(label.starts_with("[label=\"(") && label.ends_with(")\"]"));
+            nodes.insert(node.to_string());
+        }
+    }
+    assert_eq!(edges, htree.len());
+    // endpoints of an HTree never coincide
+    assert_eq!(nodes.len(), 2 * htree.len());
+}
+
+#[test]
+fn test_planarized_has_no_t_junctions() {
+    for htree in [HTree::<f64>::new(6), HTree::with_branching(3, 3)] {
+        let pieces: Vec<_> = htree.planarized().collect();
+        for &(start, stop) in &pieces {
+            for &(other_start, other_stop) in &pieces {
+                for point in [other_start, other_stop] {
+                    let (dx, dy) = (stop.0 - start.0, stop.1 - start.1);
+                    let along = ((point.0 - start.0) * dx + (point.1 - start.1) * dy) / (dx * dx + dy * dy);
+                    let across = ((point.0 - start.0) * dy - (point.1 - start.1) * dx).abs();
+                    let inside = across < 1e-12 && along > 1e-12 && along < 1.0 - 1e-12;
+                    assert!(!inside, "{point:?} lies inside a piece");
+                }
+            }
+        }
+        let total: f64 = pieces.iter().map(|&(a, b)| length(a, b)).sum();
+        assert!((total - htree.total_length()).abs() < 1e-9);
+    }
+    // every line but the root is split where it touches its parent
+    let htree: HTree<f64> = HTree::new(6);
+    assert_eq!(htree.planarized().count(), 2 * htree.len() - 1);
+    // with 3 branches the middle children cross their parents and both are split there
+    let htree: HTree<f64> = HTree::with_branching(1, 3);
+    assert_eq!(htree.planarized().count(), 2 + 2 + 2 + 2);
+}
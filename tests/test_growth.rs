@@ -0,0 +1,162 @@
+use htree::{HTree, Orientation, Shape};
+
+#[test]
+fn test_growth_report_matches_iteration() {
+    let order = 8;
+    let htree: HTree<f64> = HTree::new(order);
+    let report = htree.growth_report();
+
+    // lines shrink with every level, so grouping by length recovers the levels
+    let mut lengths: Vec<f64> = htree
+        .into_iter()
+        .map(|(start, stop)| ((stop.0 - start.0).powi(2) + (stop.1 - start.1).powi(2)).sqrt())
+        .collect();
+    lengths.sort_by(|a, b| b.partial_cmp(a).unwrap());
+    let mut levels: Vec<(f64, usize)> = Vec::new();
+    for length in lengths {
+        match levels.last_mut() {
+            Some((level_length, count)) if (*level_length - length).abs() < 1e-9 => *count += 1,
+            _ => levels.push((length, 1)),
+        }
+    }
+
+    assert_eq!(levels.len(), order + 1);
+    assert_eq!(
+        levels.iter().map(|(_, count)| *count).collect::<Vec<_>>(),
+        report.segments_per_level
+    );
+    for ((length, _), expected) in levels.iter().zip(report.segment_lengths.iter()) {
+        assert!((length - expected).abs() < 1e-9);
+    }
+
+    let total: f64 = levels.iter().map(|(length, count)| length * *count as f64).sum();
+    assert!((total - report.cumulative_lengths[order]).abs() < 1e-9);
+    assert!((report.scaling_ratio - 0.5f64.sqrt()).abs() < 1e-12);
+    assert!((report.fractal_dimension - 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_with_axis_orders() {
+    for order in 0..8 {
+        let expected: Vec<_> = HTree::<f64>::new(order).into_iter().collect();
+        let htree: HTree<f64> = HTree::with_axis_orders(order, order);
+        assert_eq!(htree.into_iter().collect::<Vec<_>>(), expected);
+    }
+    let counts = |htree: HTree<f64>| {
+        let horizontal = htree
+            .iter_with_orientation()
+            .filter(|&(orientation, _)| orientation == Orientation::Horizontal)
+            .count();
+        (horizontal, htree.len() - horizontal)
+    };
+    // horizontal lines one level deeper, level 4 adds only horizontal lines
+    assert_eq!(counts(HTree::with_axis_orders(4, 3)), (1 + 4 + 16, 2 + 8));
+    // vertical lines one level deeper, levels 1 and 5 add only vertical lines
+    assert_eq!(counts(HTree::with_axis_orders(4, 5)), (1 + 4 + 16, 2 + 8 + 32));
+    assert_eq!(counts(HTree::with_axis_orders(0, 1)), (1, 2));
+    // the next level of the deeper direction runs the other way and adds nothing
+    assert_eq!(counts(HTree::with_axis_orders(3, 4)), counts(HTree::with_axis_orders(3, 3)));
+    assert_eq!(counts(HTree::with_axis_orders(1, 0)), (1, 0));
+}
+
+#[test]
+#[should_panic(expected = "at most one level deeper")]
+fn test_with_axis_orders_rejects_distant_orders() {
+    HTree::<f64>::with_axis_orders(10, 2);
+}
+
+#[test]
+fn test_with_budget_keeps_whole_levels() {
+    for htree in [HTree::<f64>::new(8), HTree::with_branching(4, 3)] {
+        // number of lines of the first `levels` levels
+        let sizes: Vec<usize> = (0..=htree.order() + 1)
+            .map(|levels| (0..levels).map(|level| htree.level(level).count()).sum())
+            .collect();
+        for max_segments in 0..htree.len() + 10 {
+            let count = htree.with_budget(max_segments).count();
+            assert!(count <= max_segments);
+            let levels = sizes.iter().position(|&size| size == count).expect("not a prefix of whole levels");
+            assert!(levels > htree.order() || sizes[levels + 1] > max_segments);
+        }
+        let lines: Vec<_> = htree.with_budget(20).collect();
+        assert_eq!(lines, htree.into_iter().take(lines.len()).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn test_ifs_maps_carry_levels_onto_the_next() {
+    type Line = ((f64, f64), (f64, f64));
+    let normalized = |mut lines: Vec<Line>| {
+        let key = |value: f64| (value * 1e9).round() as i64;
+        for line in lines.iter_mut() {
+            if (key(line.1 .0), key(line.1 .1)) < (key(line.0 .0), key(line.0 .1)) {
+                *line = (line.1, line.0);
+            }
+        }
+        lines.sort_by_key(|&(a, b)| (key(a.0), key(a.1), key(b.0), key(b.1)));
+        lines
+    };
+    for htree in [HTree::<f64>::new(6), HTree::new(6).with_shape(Shape::Square), HTree::with_branching(4, 3)] {
+        let maps = htree.ifs_maps();
+        assert_eq!(maps.len(), htree.branches() as usize);
+        let apply = |m: [[f64; 3]; 3], (x, y): (f64, f64)| {
+            (m[0][0] * x + m[0][1] * y + m[0][2], m[1][0] * x + m[1][1] * y + m[1][2])
+        };
+        for level in 0..htree.order() {
+            let images: Vec<Line> = maps
+                .iter()
+                .flat_map(|&m| htree.level(level).map(move |(start, stop)| (apply(m, start), apply(m, stop))))
+                .collect();
+            let images = normalized(images);
+            let expected = normalized(htree.level(level + 1).collect());
+            assert_eq!(images.len(), expected.len());
+            for (image, line) in images.iter().zip(&expected) {
+                let distance = (image.0 .0 - line.0 .0).abs()
+                    + (image.0 .1 - line.0 .1).abs()
+                    + (image.1 .0 - line.1 .0).abs()
+                    + (image.1 .1 - line.1 .1).abs();
+                assert!(distance < 1e-12, "level {level}: {image:?} differs from {line:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_lerp_ends_at_both_htrees() {
+    for (low, high) in [(3, 4), (4, 6)] {
+        let coarse: HTree<f64> = HTree::new(low);
+        let fine: HTree<f64> = HTree::new(high);
+        let (coarse_lines, fine_lines): (Vec<_>, Vec<_>) = (coarse.into_iter().collect(), fine.into_iter().collect());
+        assert_eq!(coarse.lerp(&fine, 0.0), coarse_lines);
+        assert_eq!(coarse.lerp(&fine, 1.0), fine_lines);
+        assert_eq!(fine.lerp(&coarse, 0.0), fine_lines);
+        assert_eq!(fine.lerp(&coarse, 1.0), coarse_lines);
+
+        // halfway the new lines are half as long and still centered where they branch off
+        let halfway = coarse.lerp(&fine, 0.5);
+        assert_eq!(halfway.len(), fine.len());
+        for (morphed, line) in halfway.iter().zip(&fine_lines).skip(coarse.len()) {
+            let middle = |(start, stop): ((f64, f64), (f64, f64))| ((start.0 + stop.0) / 2.0, (start.1 + stop.1) / 2.0);
+            let length = |(start, stop): ((f64, f64), (f64, f64))| (stop.0 - start.0).hypot(stop.1 - start.1);
+            assert_eq!(middle(*morphed), middle(*line));
+            assert!((length(*morphed) - length(*line) / 2.0).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_order_for_max_length() {
+    let aspect = 0.5f64.sqrt();
+    for max_len in [1.0, 2.5, 20.0, 311.0, 1e4] {
+        let order = HTree::<f64>::order_for_max_length(max_len, aspect);
+        let total = |order: usize| HTree::<f64>::with_size(order, 1.0, aspect).total_length();
+        assert!(total(order) <= max_len);
+        assert!(total(order + 1) > max_len);
+        let lines: f64 = HTree::<f64>::new(order)
+            .into_iter()
+            .map(|(start, stop)| (stop.0 - start.0).hypot(stop.1 - start.1))
+            .sum();
+        assert!(lines <= max_len + 1e-9);
+    }
+    assert_eq!(HTree::<f64>::order_for_max_length(0.1, aspect), 0);
+}
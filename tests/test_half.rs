@@ -0,0 +1,15 @@
+#![cfg(feature = "half")]
+
+use htree::HTree;
+
+#[test]
+fn test_flat_f16_matches_flat_f32() {
+    let htree: HTree<f64> = HTree::new(12);
+    let single = htree.flat_f32();
+    let half = htree.flat_f16();
+    assert_eq!(half.len(), 4 * htree.len());
+    for (half, single) in half.iter().zip(single) {
+        // half precision rounds to 11 significant bits
+        assert!((half.to_f32() - single).abs() <= single * 2f32.powi(-11));
+    }
+}
@@ -0,0 +1,22 @@
+use htree::{to_hpgl, HTree};
+
+#[test]
+fn test_hpgl_selects_one_pen_per_level() {
+    let htree: HTree<f64> = HTree::new(6);
+    let hpgl = to_hpgl(&htree, 10_000, 7_000);
+    assert!(hpgl.starts_with("IN;PA;") && hpgl.ends_with("PU;SP0;"));
+    let pens: Vec<&str> = hpgl.split(';').filter(|command| command.starts_with("SP")).collect();
+    let expected: Vec<String> = (1..=htree.order() + 1).map(|pen| format!("SP{pen}")).chain(["SP0".into()]).collect();
+    assert_eq!(pens, expected);
+
+    let coordinates: Vec<(i64, i64)> = hpgl
+        .split(';')
+        .filter(|command| (command.starts_with("PU") || command.starts_with("PD")) && command.len() > 2)
+        .map(|command| {
+            let (x, y) = command[2..].split_once(',').unwrap();
+            (x.parse().unwrap(), y.parse().unwrap())
+        })
+        .collect();
+    assert_eq!(coordinates.len(), 2 * htree.len());
+    assert!(coordinates.iter().all(|&(x, y)| (0..=10_000).contains(&x) && (0..=7_000).contains(&y)));
+}
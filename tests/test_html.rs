@@ -0,0 +1,17 @@
+use htree::{to_html_canvas, HTree};
+
+#[test]
+fn test_html_canvas_inlines_all_lines() {
+    let htree: HTree<f64> = HTree::new(5);
+    let html = to_html_canvas(&htree, 640, 480);
+    assert!(html.contains("<canvas width=\"640\" height=\"480\"></canvas>"));
+    assert!(html.contains("<script>") && html.contains("</script>"));
+
+    let start = html.find("const lines = [").unwrap() + "const lines = [".len();
+    let end = start + html[start..].find(']').unwrap();
+    let numbers: Vec<f64> = html[start..end].split(',').map(|number| number.parse().unwrap()).collect();
+    assert_eq!(numbers.len(), 4 * htree.len());
+    for number in numbers.chunks(2) {
+        assert!((0.0..=640.0).contains(&number[0]) && (0.0..=480.0).contains(&number[1]));
+    }
+}
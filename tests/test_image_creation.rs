@@ -1,8 +1,9 @@
-use htree::HTree;
-use image::{ImageBuffer, Luma};
-use imageproc::drawing::draw_line_segment_mut;
+#[cfg(feature = "image")]
+use htree::{render_growth_frames, render_to_png, render_to_png_bytes, thumbnail, HTree};
 
 #[test]
+#[cfg(feature = "image")]
+#[allow(clippy::useless_vec)]
 fn test_htree_creation() {
     for order in vec![2, 6, 10, 14] {
         //lines of Htree will be contained in rectangle [[0,1],[0,1/sqrt(2)]]
@@ -15,21 +16,82 @@ fn test_htree_creation() {
         let width = (scale * 1f32) as u32;
         let height = (scale * 1f32 / 2f32.sqrt()) as u32;
 
-        let mut image: ImageBuffer<Luma<u8>, Vec<u8>> = ImageBuffer::new(width, height);
-        // white background
-        image.fill(255u8);
-
-        let black = Luma([0u8]);
-        for (start, stop) in htree.into_iter().map(|(start, stop)| {
-            (
-                (start.0 * scale, start.1 * scale),
-                (stop.0 * scale, stop.1 * scale),
-            )
-        }) {
-            // Draw a line of the HTree in black
-            draw_line_segment_mut(&mut image, (start.0, start.1), (stop.0, stop.1), black);
-        }
         let path=format!("resources/example_htree_order_{order}.png");
-        assert!(image.save(path).is_ok());
+        assert!(render_to_png(&htree, path, width, height).is_ok());
     }
 }
+
+#[test]
+#[cfg(feature = "image")]
+fn test_render_to_png() {
+    let htree: HTree<f32> = HTree::new(6);
+    let path = std::env::temp_dir().join("htree_render_to_png_order_6.png");
+    render_to_png(&htree, &path, 300, 212).unwrap();
+
+    assert!(path.exists());
+    let image = image::open(&path).unwrap();
+    assert_eq!((image.width(), image.height()), (300, 212));
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn test_render_to_png_bytes() {
+    let htree: HTree<f32> = HTree::new(5);
+    let bytes = render_to_png_bytes(&htree, 320, 226).unwrap();
+    let image = image::load_from_memory_with_format(&bytes, image::ImageFormat::Png).unwrap();
+    assert_eq!((image.width(), image.height()), (320, 226));
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn test_render_growth_frames() {
+    let htree: HTree<f32> = HTree::new(5);
+    let frames = render_growth_frames(&htree, 200, 141);
+    assert_eq!(frames.len(), htree.order() + 1);
+    for frame in frames {
+        let image = image::load_from_memory_with_format(&frame, image::ImageFormat::Png).unwrap();
+        assert_eq!((image.width(), image.height()), (200, 141));
+    }
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn test_coverage_buffer() {
+    let htree: HTree<f32> = HTree::new(3);
+    let (width, height) = (160u32, 120u32);
+    let coverage = htree.coverage_buffer(width, height, 2.0);
+    assert_eq!(coverage.len(), (width * height) as usize);
+    assert!(coverage.iter().all(|value| (0.0..=1.0).contains(value)));
+    // scale of the rasterizer, the HTree spans the full width
+    let scale = width as f32;
+    let offset = (height as f32 - scale * 0.5f32.sqrt()) / 2.0;
+    let at = |x: f32, y: f32| coverage[(offset + y * scale) as usize * width as usize + (x * scale) as usize];
+    for (start, stop) in htree {
+        let middle = ((start.0 + stop.0) / 2.0, (start.1 + stop.1) / 2.0);
+        // pixel centers are at most half a pixel off the centerline
+        assert_eq!(at(middle.0, middle.1), 1.0);
+    }
+    // corners of the bounding box are far from every line
+    assert_eq!(coverage[0], 0.0);
+    assert_eq!(coverage[coverage.len() - 1], 0.0);
+    assert_eq!(at(0.01, 0.01), 0.0);
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn test_thumbnail() {
+    // far too many lines to draw unless the order is capped
+    let htree: HTree<f32> = HTree::new(30);
+    let image = thumbnail(&htree, 96);
+    assert_eq!(image.width().max(image.height()), 96);
+    assert_eq!(image.height(), 68);
+    assert!(image.pixels().any(|pixel| pixel.0 == [0, 0, 0, 255]));
+    assert!(image.pixels().any(|pixel| pixel.0 == [255, 255, 255, 255]));
+
+    let tall: HTree<f32> = HTree::with_size(6, 300.0, 800.0);
+    assert_eq!(thumbnail(&tall, 160).dimensions(), (60, 160));
+
+    // lines halve every level with 4 branches, so only a handful of levels are longer than a pixel
+    let branched: HTree<f32> = HTree::with_branching(20, 4);
+    assert_eq!(thumbnail(&branched, 96), thumbnail(&HTree::with_branching(8, 4), 96));
+}
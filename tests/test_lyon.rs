@@ -0,0 +1,20 @@
+#![cfg(feature = "lyon")]
+
+use htree::{path_events, HTree};
+use lyon::path::{Event, Path};
+
+#[test]
+fn test_path_events_match_polylines() {
+    let htree: HTree<f32> = HTree::new(5);
+    let mut builder = Path::builder();
+    for event in path_events(&htree) {
+        builder.path_event(event);
+    }
+    let path = builder.build();
+
+    let polylines = htree.polylines();
+    let lines = path.iter().filter(|event| matches!(event, Event::Line { .. })).count();
+    let subpaths = path.iter().filter(|event| matches!(event, Event::Begin { .. })).count();
+    assert_eq!(subpaths, polylines.len());
+    assert_eq!(lines, polylines.iter().map(|polyline| polyline.len() - 1).sum::<usize>());
+}
@@ -0,0 +1,18 @@
+#![cfg(feature = "prost")]
+
+use htree::{to_protobuf, HTree, HTreeSegments};
+use prost::Message;
+
+#[test]
+fn test_protobuf_round_trip() {
+    let htree: HTree<f64> = HTree::new(7);
+    let decoded = HTreeSegments::decode(to_protobuf(&htree).as_slice()).unwrap();
+    let lines: Vec<_> = htree.into_iter().collect();
+    assert_eq!(decoded.segments.len(), lines.len());
+    for (segment, line) in [
+        (decoded.segments.first().unwrap(), lines.first().unwrap()),
+        (decoded.segments.last().unwrap(), lines.last().unwrap()),
+    ] {
+        assert_eq!(((segment.x0, segment.y0), (segment.x1, segment.y1)), *line);
+    }
+}
@@ -0,0 +1,23 @@
+#![cfg(feature = "raqote")]
+
+use htree::{draw_into_raqote, HTree};
+use raqote::{DrawTarget, SolidSource, Source, StrokeStyle, Transform};
+
+#[test]
+fn test_draw_into_raqote() {
+    let htree: HTree<f32> = HTree::new(4);
+    let scale = 64f32;
+    let mut dt = DrawTarget::new(64, 46);
+    dt.clear(SolidSource::from_unpremultiplied_argb(255, 255, 255, 255));
+    dt.set_transform(&Transform::scale(scale, scale));
+
+    let black = Source::Solid(SolidSource::from_unpremultiplied_argb(255, 0, 0, 0));
+    let stroke = StrokeStyle {
+        width: 1.0 / scale,
+        ..Default::default()
+    };
+    draw_into_raqote(&htree, &mut dt, &black, &stroke);
+
+    let background = dt.get_data()[0];
+    assert!(dt.get_data().iter().any(|&pixel| pixel != background));
+}
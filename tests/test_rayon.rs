@@ -0,0 +1,20 @@
+#![cfg(feature = "rayon")]
+
+use htree::HTree;
+use rayon::prelude::*;
+
+#[test]
+fn test_parallel_borrow_matches_sequential() {
+    let htree: HTree<f64> = HTree::new(11);
+    let parallel: Vec<_> = (&htree).into_par_iter().collect();
+    let sequential: Vec<_> = (&htree).into_iter().collect();
+    assert_eq!(parallel.len(), sequential.len());
+
+    let sort = |mut lines: Vec<((f64, f64), (f64, f64))>| {
+        lines.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        lines
+    };
+    assert_eq!(sort(parallel.clone()), sort(sequential.clone()));
+    // indexed, so collecting keeps the order
+    assert_eq!(parallel, sequential);
+}
@@ -0,0 +1,52 @@
+use htree::HTree;
+
+type Line = ((f64, f64), (f64, f64));
+
+/// Builds the H tree of the given order straight from its recursive definition.
+///
+/// The first line is horizontal, centered in the `1 x 1/sqrt(2)` box and half as long as the box is wide.
+/// Every line carries two perpendicular lines centered on its endpoints which are `1/sqrt(2)` times as long.
+fn reference_htree(order: usize) -> Vec<Line> {
+    fn add(lines: &mut Vec<Line>, center: (f64, f64), length: f64, horizontal: bool, remaining: usize) {
+        let offset = if horizontal { (length / 2.0, 0.0) } else { (0.0, length / 2.0) };
+        let start = (center.0 - offset.0, center.1 - offset.1);
+        let stop = (center.0 + offset.0, center.1 + offset.1);
+        lines.push((start, stop));
+        if remaining > 0 {
+            for end in [start, stop] {
+                add(lines, end, length * 0.5f64.sqrt(), !horizontal, remaining - 1);
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    add(&mut lines, (0.5, 0.5f64.sqrt() / 2.0), 0.5, true, order);
+    lines
+}
+
+/// Returns the lines with their endpoints ordered and sorted, so equal sets of lines compare equal.
+fn normalized(lines: impl IntoIterator<Item = Line>) -> Vec<Line> {
+    let key = |value: f64| (value * 1e9).round() as i64;
+    let mut lines: Vec<Line> = lines
+        .into_iter()
+        .map(|(a, b)| if (key(a.0), key(a.1)) <= (key(b.0), key(b.1)) { (a, b) } else { (b, a) })
+        .collect();
+    lines.sort_by_key(|&(a, b)| (key(a.0), key(a.1), key(b.0), key(b.1)));
+    lines
+}
+
+#[test]
+fn test_matches_reference_definition() {
+    for order in 0..=6 {
+        let expected = normalized(reference_htree(order));
+        let actual = normalized(HTree::<f64>::new(order));
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(&expected) {
+            let distance = (actual.0 .0 - expected.0 .0).abs()
+                + (actual.0 .1 - expected.0 .1).abs()
+                + (actual.1 .0 - expected.1 .0).abs()
+                + (actual.1 .1 - expected.1 .1).abs();
+            assert!(distance < 1e-12, "order {order}: {actual:?} differs from {expected:?}");
+        }
+    }
+}
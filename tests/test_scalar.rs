@@ -0,0 +1,139 @@
+use htree::HTree;
+use num::{Float, Num, NumCast, One, ToPrimitive, Zero};
+use std::num::FpCategory;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Scalar which only accepts conversions of integral values, like `T::from(2)` but not `T::from(0.5)`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+struct Integral(f64);
+
+macro_rules! binary {
+    ($($trait:ident $method:ident),*) => {$(
+        impl $trait for Integral {
+            type Output = Integral;
+            fn $method(self, other: Integral) -> Integral {
+                Integral(self.0.$method(other.0))
+            }
+        }
+    )*};
+}
+binary!(Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+impl Neg for Integral {
+    type Output = Integral;
+    fn neg(self) -> Integral {
+        Integral(-self.0)
+    }
+}
+
+impl Zero for Integral {
+    fn zero() -> Integral {
+        Integral(0.0)
+    }
+    fn is_zero(&self) -> bool {
+        self.0 == 0.0
+    }
+}
+
+impl One for Integral {
+    fn one() -> Integral {
+        Integral(1.0)
+    }
+}
+
+impl Num for Integral {
+    type FromStrRadixErr = num::traits::ParseFloatError;
+    fn from_str_radix(s: &str, radix: u32) -> Result<Integral, Self::FromStrRadixErr> {
+        f64::from_str_radix(s, radix).map(Integral)
+    }
+}
+
+impl ToPrimitive for Integral {
+    fn to_i64(&self) -> Option<i64> {
+        self.0.to_i64()
+    }
+    fn to_u64(&self) -> Option<u64> {
+        self.0.to_u64()
+    }
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.0)
+    }
+}
+
+impl NumCast for Integral {
+    fn from<N: ToPrimitive>(n: N) -> Option<Integral> {
+        let value = n.to_f64()?;
+        (value.fract() == 0.0).then_some(Integral(value))
+    }
+}
+
+macro_rules! forward {
+    ($($method:ident),*) => {$(
+        fn $method(self) -> Integral {
+            Integral(self.0.$method())
+        }
+    )*};
+}
+macro_rules! forward_binary {
+    ($($method:ident),*) => {$(
+        fn $method(self, other: Integral) -> Integral {
+            Integral(self.0.$method(other.0))
+        }
+    )*};
+}
+macro_rules! constant {
+    ($($method:ident),*) => {$(
+        fn $method() -> Integral {
+            Integral(f64::$method())
+        }
+    )*};
+}
+macro_rules! predicate {
+    ($($method:ident),*) => {$(
+        fn $method(self) -> bool {
+            self.0.$method()
+        }
+    )*};
+}
+
+impl Float for Integral {
+    constant!(nan, infinity, neg_infinity, neg_zero, min_value, min_positive_value, max_value);
+    predicate!(is_nan, is_infinite, is_finite, is_normal, is_sign_positive, is_sign_negative);
+    forward!(
+        floor, ceil, round, trunc, fract, abs, signum, recip, sqrt, exp, exp2, ln, log2, log10, cbrt, sin, cos,
+        tan, asin, acos, atan, exp_m1, ln_1p, sinh, cosh, tanh, asinh, acosh, atanh
+    );
+    forward_binary!(powf, log, max, min, hypot, atan2);
+
+    fn classify(self) -> FpCategory {
+        self.0.classify()
+    }
+    fn mul_add(self, a: Integral, b: Integral) -> Integral {
+        Integral(self.0.mul_add(a.0, b.0))
+    }
+    fn powi(self, n: i32) -> Integral {
+        Integral(self.0.powi(n))
+    }
+    #[allow(deprecated)]
+    fn abs_sub(self, other: Integral) -> Integral {
+        Integral((self.0 - other.0).max(0.0))
+    }
+    fn sin_cos(self) -> (Integral, Integral) {
+        (self.sin(), self.cos())
+    }
+    fn integer_decode(self) -> (u64, i16, i8) {
+        self.0.integer_decode()
+    }
+}
+
+#[test]
+fn test_iteration_without_fractional_conversions() {
+    assert!(<Integral as NumCast>::from(0.5).is_none());
+    let htree: HTree<Integral> = HTree::new(8);
+    let reference: HTree<f64> = HTree::new(8);
+    let lines: Vec<_> = htree.into_iter().collect();
+    assert_eq!(lines.len(), reference.len());
+    for (((a, b), (c, d)), ((e, f), (g, h))) in lines.into_iter().zip(reference) {
+        assert_eq!([a.0, b.0, c.0, d.0], [e, f, g, h]);
+    }
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "serde")]
+
+use htree::{EmissionOrder, HTree, Shape};
+
+#[test]
+fn test_round_trip_keeps_every_option() {
+    let htree: HTree<f64> = HTree::with_branching(4, 3)
+        .with_shape(Shape::Square)
+        .with_emission_order(EmissionOrder::DepthFirst);
+    let json = serde_json::to_string(&htree).unwrap();
+    let restored: HTree<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        (restored.order(), restored.branches(), restored.shape(), restored.emission_order()),
+        (4, 3, Shape::Square, EmissionOrder::DepthFirst)
+    );
+
+    let bytes = |h_tree: HTree<f64>| -> Vec<u8> {
+        h_tree
+            .into_iter()
+            .flat_map(|((a, b), (c, d))| [a, b, c, d])
+            .flat_map(f64::to_le_bytes)
+            .collect()
+    };
+    assert_eq!(bytes(restored), bytes(htree));
+}
+
+#[test]
+fn test_checkpoint_round_trip() {
+    let htree: HTree<f64> = HTree::new(6);
+    let mut lines = htree.into_iter();
+    lines.nth(20);
+    let json = serde_json::to_string(&lines.checkpoint()).unwrap();
+    let resumed = htree.resume_from(serde_json::from_str(&json).unwrap());
+    assert!(resumed.eq(lines));
+}
+
+#[test]
+fn test_deserialize_rejects_too_few_branches() {
+    let json = serde_json::to_string(&HTree::<f64>::new(3)).unwrap();
+    for branches in [0, 1] {
+        let invalid = json.replace("\"branches\":2", &format!("\"branches\":{branches}"));
+        assert_ne!(invalid, json);
+        let error = serde_json::from_str::<HTree<f64>>(&invalid).unwrap_err();
+        assert!(error.to_string().contains("at least 2 branches"), "{error}");
+    }
+}
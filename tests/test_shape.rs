@@ -0,0 +1,93 @@
+use htree::{HTree, Shape};
+
+type Line = ((f64, f64), (f64, f64));
+
+fn assert_lines_eq(actual: &[Line], expected: &[Line]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        for (x, y) in [(a.0 .0, e.0 .0), (a.0 .1, e.0 .1), (a.1 .0, e.1 .0), (a.1 .1, e.1 .1)] {
+            assert!((x - y).abs() < 1e-12, "{a:?} != {e:?}");
+        }
+    }
+}
+
+#[test]
+fn test_shapes_first_level() {
+    let h = 0.5f64.sqrt();
+    let classic: Vec<_> = HTree::new(1).into_iter().collect();
+    assert_lines_eq(
+        &classic,
+        &[
+            ((0.25, 0.5 * h), (0.75, 0.5 * h)),
+            ((0.25, 0.25 * h), (0.25, 0.75 * h)),
+            ((0.75, 0.25 * h), (0.75, 0.75 * h)),
+        ],
+    );
+
+    let square: Vec<_> = HTree::new(1).with_shape(Shape::Square).into_iter().collect();
+    assert_lines_eq(
+        &square,
+        &[
+            ((0.25, 0.5), (0.75, 0.5)),
+            ((0.25, 0.25), (0.25, 0.75)),
+            ((0.75, 0.25), (0.75, 0.75)),
+        ],
+    );
+}
+
+#[test]
+fn test_square_shape_scaling() {
+    let report = HTree::<f64>::new(4).with_shape(Shape::Square).growth_report();
+    assert_eq!(report.segment_lengths, vec![0.5, 0.5, 0.25, 0.25, 0.125]);
+}
+
+#[test]
+fn test_bounds_first_segment() {
+    // the bounding box of 1 x 1/sqrt(2) is scaled by 2 and centered vertically in the 2 x 2 square
+    let preserved: HTree<f64> = HTree::new(10).with_bounds((10.0, 20.0), (12.0, 22.0));
+    assert_lines_eq(&[preserved.segment_at(0).unwrap()], &[((10.5, 21.0), (11.5, 21.0))]);
+
+    let stretched: HTree<f64> = HTree::new(10).with_stretched_bounds((-1.0, -1.0), (3.0, 1.0));
+    assert_lines_eq(&[stretched.segment_at(0).unwrap()], &[((0.0, 0.0), (2.0, 0.0))]);
+    assert_lines_eq(&[stretched.segment_at(1).unwrap()], &[((0.0, -0.5), (0.0, 0.5))]);
+
+    let flipped = stretched.with_flipped_y();
+    assert_lines_eq(&[flipped.segment_at(0).unwrap()], &[((0.0, 0.0), (2.0, 0.0))]);
+    assert_lines_eq(&[flipped.segment_at(1).unwrap()], &[((0.0, 0.5), (0.0, -0.5))]);
+    assert_eq!(flipped.with_flipped_y().segment_at(1), stretched.segment_at(1));
+}
+
+#[test]
+fn test_bounds_queries() {
+    let htree: HTree<f64> = HTree::new(6).with_bounds((-3.0, 5.0), (1.0, 6.0)).with_flipped_y();
+    let lines: Vec<_> = htree.into_iter().collect();
+    assert_eq!(htree.collect_all(), lines);
+    let (min, max) = ((-2.5, 5.2), (-1.0, 5.6));
+    let mut inside: Vec<_> = htree.segments_in_rect(min, max).collect();
+    let mut expected: Vec<_> = lines
+        .iter()
+        .copied()
+        .filter(|&(start, stop)| {
+            let overlaps = |a: f64, b: f64, low: f64, high: f64| a.min(b) <= high && a.max(b) >= low;
+            overlaps(start.0, stop.0, min.0, max.0) && overlaps(start.1, stop.1, min.1, max.1)
+        })
+        .collect();
+    inside.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert!(!expected.is_empty());
+    assert_eq!(inside, expected);
+    let p = (-1.2, 5.3);
+    let index = htree.build_spatial_index(8);
+    assert_eq!(htree.segment_at(index.nearest(p).0).unwrap(), htree.nearest_segment(p));
+    assert_eq!(index.nearest(p).1, htree.distance_to(p));
+
+    // moving the bounding box leaves everything relative to it unchanged
+    let moved = HTree::<f64>::new(6).with_stretched_bounds((2.0, 3.0), (3.0, 3.0 + 0.5f64.sqrt())).with_flipped_y();
+    assert!((moved.fill_ratio(0.05) - HTree::<f64>::new(6).fill_ratio(0.05)).abs() < 1e-12);
+    assert_eq!(moved.symmetries(), HTree::<f64>::new(6).symmetries());
+    let (start, _) = moved.segment_at(0).unwrap();
+    let m = moved.ifs_maps()[1];
+    let image = (m[0][0] * start.0 + m[0][1] * start.1 + m[0][2], m[1][0] * start.0 + m[1][1] * start.1 + m[1][2]);
+    let (child, _) = moved.segment_at(2).unwrap();
+    assert!((image.0 - child.0).abs() < 1e-12 && (image.1 - child.1).abs() < 1e-12);
+}
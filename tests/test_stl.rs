@@ -0,0 +1,25 @@
+use htree::{to_extruded_stl, HTree};
+
+#[test]
+fn test_extruded_stl_has_twelve_triangles_per_line() {
+    let htree: HTree<f32> = HTree::with_branching(4, 3);
+    let stl = to_extruded_stl(&htree, 0.005, 0.02);
+    let triangles = u32::from_le_bytes(stl[80..84].try_into().unwrap()) as usize;
+    assert_eq!(triangles, 12 * htree.len());
+    assert_eq!(stl.len(), 84 + 50 * triangles);
+
+    for triangle in stl[84..].chunks(50) {
+        let floats: Vec<f32> =
+            triangle[..48].chunks(4).map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap())).collect();
+        let normal = &floats[..3];
+        assert!((normal.iter().map(|component| component * component).sum::<f32>() - 1.0).abs() < 1e-5);
+        // counterclockwise seen from outside, the winding agrees with the normal
+        let vertex = |i: usize| &floats[3 + 3 * i..6 + 3 * i];
+        let (a, b, c) = (vertex(0), vertex(1), vertex(2));
+        let (u, v) = ([b[0] - a[0], b[1] - a[1], b[2] - a[2]], [c[0] - a[0], c[1] - a[1], c[2] - a[2]]);
+        let cross = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+        assert!(cross.iter().zip(normal).map(|(a, b)| a * b).sum::<f32>() > 0.0);
+        assert!(vertex(0)[2] == 0.0 || vertex(0)[2] == 0.02);
+        assert_eq!(triangle[48..], [0, 0]);
+    }
+}
@@ -0,0 +1,60 @@
+use htree::HTree;
+
+#[test]
+fn test_corner_radius_adds_arcs() {
+    let htree: HTree<f64> = HTree::new(4);
+    let sharp = htree.to_svg(0.002, 0.0);
+    assert!(sharp.starts_with("<svg") && sharp.ends_with("</svg>"));
+    assert_eq!(sharp.matches('A').count(), 0);
+    assert_eq!(sharp.matches('L').count(), htree.len());
+
+    let rounded = htree.to_svg(0.002, 0.01);
+    // every line but the deepest ends in two junctions with two fillets each
+    let junction_lines = htree.len() - htree.level(4).count();
+    assert_eq!(rounded.matches('A').count(), 4 * junction_lines);
+    assert_eq!(rounded.matches('L').count(), htree.len());
+}
+
+#[test]
+fn test_fillets_end_on_the_children() {
+    let htree: HTree<f64> = HTree::new(1);
+    let radius = 0.05;
+    let svg = htree.to_svg(0.01, radius);
+    let ends: Vec<(f64, f64)> = svg
+        .split('A')
+        .skip(1)
+        .map(|arc| {
+            let arguments = arc.split(['M', '"']).next().unwrap();
+            let values: Vec<f64> = arguments.split(' ').map(|value| value.parse().unwrap()).collect();
+            assert_eq!(values[..4], [radius, radius, 0.0, 0.0]);
+            (values[5], values[6])
+        })
+        .collect();
+    assert_eq!(ends.len(), 4);
+    let children: Vec<_> = htree.level(1).collect();
+    for end in ends {
+        // fillets end `radius` away from the middle of a child
+        let on_child = children.iter().any(|&(start, stop)| {
+            let middle = ((start.0 + stop.0) / 2.0, (start.1 + stop.1) / 2.0);
+            (end.0 - start.0).abs() < 1e-12 && ((end.1 - middle.1).abs() - radius).abs() < 1e-12
+        });
+        assert!(on_child, "{end:?} is not on a child");
+    }
+}
+
+#[test]
+fn test_layers_hold_their_levels() {
+    let htree: HTree<f32> = HTree::new(6);
+    let svg = htree::to_svg_layered(&htree, 1000.0, 707.0);
+    assert_eq!(svg.matches("<g ").count(), htree.order() + 1);
+    assert_eq!(svg.matches("</g>").count(), htree.order() + 1);
+    assert_eq!(svg.matches("<line ").count(), htree.len());
+    for (level, group) in svg.split("<g ").skip(1).enumerate() {
+        assert!(group.starts_with(&format!("inkscape:label=\"level {level}\"")));
+        assert_eq!(group.matches("<line ").count(), 1 << level);
+    }
+    // coordinates stay within the document
+    for value in svg.split('"').filter_map(|value| value.parse::<f64>().ok()) {
+        assert!((0.0..=1000.0).contains(&value));
+    }
+}
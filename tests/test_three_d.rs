@@ -0,0 +1,44 @@
+use htree::three_d::HTree3;
+
+type Point = (f64, f64, f64);
+
+fn axes(start: Point, stop: Point) -> [bool; 3] {
+    [start.0 != stop.0, start.1 != stop.1, start.2 != stop.2]
+}
+
+fn close(a: Point, b: Point) -> bool {
+    (a.0 - b.0).abs() < 1e-12 && (a.1 - b.1).abs() < 1e-12 && (a.2 - b.2).abs() < 1e-12
+}
+
+#[test]
+fn test_order_0_is_single_stroke() {
+    let lines: Vec<_> = HTree3::<f64>::new(0).into_iter().collect();
+    assert_eq!(lines.len(), 1);
+    let (start, stop) = lines[0];
+    assert_eq!(axes(start, stop), [true, false, false]);
+}
+
+#[test]
+fn test_order_1_adds_perpendicular_branches() {
+    let lines: Vec<_> = HTree3::<f64>::new(1).into_iter().collect();
+    assert_eq!(lines.len(), 3);
+    let (root_start, root_stop) = lines[0];
+    for (&(start, stop), tip) in lines[1..].iter().zip([root_start, root_stop]) {
+        assert_eq!(axes(start, stop), [false, true, false]);
+        // branches are centered on the tips of the root
+        let middle = ((start.0 + stop.0) / 2.0, (start.1 + stop.1) / 2.0, (start.2 + stop.2) / 2.0);
+        assert!(close(middle, tip));
+    }
+}
+
+#[test]
+fn test_axes_cycle() {
+    let lines: Vec<_> = HTree3::<f64>::new(6).into_iter().collect();
+    for level in 0..=6usize {
+        let mut expected = [false; 3];
+        expected[level % 3] = true;
+        for &(start, stop) in &lines[(1 << level) - 1..(1 << (level + 1)) - 1] {
+            assert_eq!(axes(start, stop), expected);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "tracing")]
+
+use htree::HTree;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata};
+
+/// Subscriber counting the trace events it receives.
+struct CountingSubscriber {
+    events: Arc<AtomicUsize>,
+}
+
+impl tracing::Subscriber for CountingSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= Level::TRACE
+    }
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+    fn event(&self, _event: &Event<'_>) {
+        self.events.fetch_add(1, Ordering::SeqCst);
+    }
+    fn enter(&self, _span: &Id) {}
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn test_next_traces_every_line() {
+    let events = Arc::new(AtomicUsize::new(0));
+    let subscriber = CountingSubscriber { events: events.clone() };
+    let htree: HTree<f32> = HTree::new(3);
+    let lines = tracing::subscriber::with_default(subscriber, || htree.into_iter().count());
+    assert_eq!(lines, 15);
+    assert_eq!(events.load(Ordering::SeqCst), 15);
+}
+
+#[test]
+fn test_fold_traces_every_line() {
+    let events = Arc::new(AtomicUsize::new(0));
+    let subscriber = CountingSubscriber { events: events.clone() };
+    let htree: HTree<f32> = HTree::new(3);
+    tracing::subscriber::with_default(subscriber, || {
+        let mut lines = htree.into_iter();
+        lines.next();
+        lines.for_each(drop);
+    });
+    assert_eq!(events.load(Ordering::SeqCst), 15);
+}